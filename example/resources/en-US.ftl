@@ -1,2 +1,4 @@
 hello-world = Hello, world!
 intro = Welcome, { $name }.
+x-input-too-short = Please use at least { $limit } characters.
+x-input-too-long = Please use no more than { $limit } characters.
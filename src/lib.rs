@@ -1,8 +1,24 @@
 pub mod xcontainer;
 #[cfg(feature="element-x-button")]
 pub mod xbutton;
+#[cfg(feature="element-x-buttons")]
+pub mod xbuttons;
 #[cfg(feature="element-x-label")]
 pub mod xlabel;
+#[cfg(feature="element-x-checkbox")]
+pub mod xcheckbox;
+#[cfg(feature="element-x-radio")]
+pub mod xradio;
+#[cfg(feature="element-x-radios")]
+pub mod xradios;
+#[cfg(feature="element-x-switch")]
+pub mod xswitch;
+#[cfg(feature="element-x-input")]
+pub mod xinput;
+#[cfg(feature="element-x-stepper")]
+pub mod xstepper;
+#[cfg(feature="element-x-numberinput")]
+pub mod xnumberinput;
 #[cfg(feature="element-x-tooltip")]
 pub mod xtooltip;
 #[cfg(all(feature="element-x-message", feature="feature-intl"))]
@@ -13,7 +29,19 @@ pub mod xmenuitem;
 mod intl;
 #[cfg(feature="element-x-menu")]
 pub mod xmenu;
+#[cfg(feature="element-x-menubar")]
+pub mod xmenubar;
+#[cfg(feature="element-x-contextmenu")]
+pub mod xcontextmenu;
+#[cfg(feature="element-x-shortcut")]
+pub mod xshortcut;
+#[cfg(feature="element-x-popover")]
+pub mod xpopover;
 mod utils;
+#[cfg(any(feature="element-x-tooltip", feature="element-x-menu"))]
+mod positioning;
+#[cfg(any(feature="element-x-button", feature="element-x-menuitem"))]
+mod ripples;
 
 /// Re-exported from `web_sys` crate.
 #[cfg(feature = "feature-intl")]
@@ -70,7 +98,7 @@ impl ToString for XComponentSize {
 }
 
 
-#[derive(PartialEq)]
+#[derive(PartialEq, Clone)]
 pub struct Transition {
     property: String,
     duration: f64,
@@ -87,7 +115,7 @@ impl Transition {
     }
 }
 
-#[derive(PartialEq)]
+#[derive(PartialEq, Clone)]
 pub enum CSSEasing {
     Linear,
     Ease,
@@ -110,7 +138,7 @@ impl ToString for CSSEasing {
     }
 }
 
-#[cfg(any(feature="element-x-button", feature="element-x-menuitem"))]
+#[cfg(any(feature="element-x-button", feature="element-x-menuitem", feature="element-x-checkbox", feature="element-x-radio", feature="element-x-switch", feature="element-x-input", feature="element-x-numberinput"))]
 pub(crate) fn calculate_computed_size(opt_custom_size: Option<XComponentSize>, default_size: ComputedSize) -> ComputedSize {
     match opt_custom_size {
         Some(custom_size) => match custom_size {
@@ -0,0 +1,451 @@
+//! Positioning of the floating elements (tooltips, menus and popovers) next to an anchor.
+//!
+//! `compute_position` works on plain rectangles in client coordinates and never touches the DOM,
+//! so the components measure the elements themselves and only pass the sizes. Its steps are
+//! enabled through `PositionOptions`:
+//!
+//! - offset: distance between the anchor and the floating element
+//! - flip: moves to another side (and swaps start/end alignment) when there is no space
+//! - shift: moves the floating element to stay inside the boundary
+//! - size constrain: limits the size to the available space
+//! - arrow: position of an arrow pointing to the center of the anchor
+//!
+//! ```
+//! let options = PositionOptions::new(Side::Bottom, Alignment::Center, boundary)
+//!     .offset(8.0)
+//!     .flip(&[Side::Top])
+//!     .shift()
+//!     .arrow(8.0);
+//! let position = compute_position(anchor, floating_width, floating_height, &options);
+//! ```
+
+/// A rectangle in client coordinates
+#[derive(PartialEq, Clone, Copy, Debug, Default)]
+pub(crate) struct Rect {
+    pub(crate) x: f64,
+    pub(crate) y: f64,
+    pub(crate) width: f64,
+    pub(crate) height: f64,
+}
+
+impl Rect {
+    pub(crate) fn new(x: f64, y: f64, width: f64, height: f64) -> Self {
+        Self {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    pub(crate) fn right(&self) -> f64 {
+        self.x + self.width
+    }
+
+    pub(crate) fn bottom(&self) -> f64 {
+        self.y + self.height
+    }
+
+    /// Start and size on the axis along the given side
+    fn cross_axis(&self, side: Side) -> (f64, f64) {
+        if side.is_vertical() {
+            (self.x, self.width)
+        } else {
+            (self.y, self.height)
+        }
+    }
+}
+
+/// Side of the anchor which the floating element is placed
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub(crate) enum Side {
+    Top,
+    Bottom,
+    Left,
+    Right,
+}
+
+impl Side {
+    pub(crate) fn opposite(&self) -> Side {
+        match self {
+            Side::Top => Side::Bottom,
+            Side::Bottom => Side::Top,
+            Side::Left => Side::Right,
+            Side::Right => Side::Left,
+        }
+    }
+
+    /// Floating element is above or below the anchor
+    pub(crate) fn is_vertical(&self) -> bool {
+        matches!(self, Side::Top | Side::Bottom)
+    }
+}
+
+/// Alignment of the floating element along the side of the anchor. `Start` is the left (or
+/// top) edge.
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub(crate) enum Alignment {
+    Start,
+    // Only tooltips are centered
+    #[cfg_attr(not(feature = "element-x-tooltip"), allow(dead_code))]
+    Center,
+    End,
+}
+
+impl Alignment {
+    fn opposite(&self) -> Alignment {
+        match self {
+            Alignment::Start => Alignment::End,
+            Alignment::Center => Alignment::Center,
+            Alignment::End => Alignment::Start,
+        }
+    }
+}
+
+/// Options for `compute_position`
+#[derive(PartialEq, Clone, Debug)]
+pub(crate) struct PositionOptions {
+    side: Side,
+    alignment: Alignment,
+    /// Area which the floating element should stay inside
+    boundary: Rect,
+    offset: f64,
+    /// Other sides to try in order, when the preferred side has no space
+    fallback_sides: Vec<Side>,
+    shift: bool,
+    constrain_size: bool,
+    /// Minimum distance of the arrow from the corners of the floating element
+    arrow_padding: Option<f64>,
+}
+
+impl PositionOptions {
+    pub(crate) fn new(side: Side, alignment: Alignment, boundary: Rect) -> Self {
+        Self {
+            side,
+            alignment,
+            boundary,
+            offset: 0.0,
+            fallback_sides: Vec::new(),
+            shift: false,
+            constrain_size: false,
+            arrow_padding: None,
+        }
+    }
+
+    /// Distance between the anchor and the floating element
+    #[cfg_attr(not(feature = "element-x-tooltip"), allow(dead_code))]
+    pub(crate) fn offset(mut self, offset: f64) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    /// Tries the given sides in order when the preferred side has no space. When no side has
+    /// enough space, the side with the most space is used. Start and end alignments are also
+    /// swapped when the floating element overflows the boundary.
+    pub(crate) fn flip(mut self, fallback_sides: &[Side]) -> Self {
+        self.fallback_sides = fallback_sides.to_vec();
+        self
+    }
+
+    /// Moves the floating element to stay inside the boundary
+    pub(crate) fn shift(mut self) -> Self {
+        self.shift = true;
+        self
+    }
+
+    /// Limits the size of the floating element to the available space
+    #[cfg_attr(not(feature = "element-x-menu"), allow(dead_code))]
+    pub(crate) fn constrain_size(mut self) -> Self {
+        self.constrain_size = true;
+        self
+    }
+
+    /// Calculates the position of an arrow pointing to the anchor, keeping the given distance
+    /// from the corners of the floating element
+    #[cfg_attr(not(feature = "element-x-tooltip"), allow(dead_code))]
+    pub(crate) fn arrow(mut self, padding: f64) -> Self {
+        self.arrow_padding = Some(padding);
+        self
+    }
+}
+
+/// Resolved position of a floating element
+#[derive(PartialEq, Clone, Debug)]
+pub(crate) struct Position {
+    pub(crate) x: f64,
+    pub(crate) y: f64,
+    /// Side after flipping
+    pub(crate) side: Side,
+    /// Alignment after flipping
+    pub(crate) alignment: Alignment,
+    /// Set when the size was constrained
+    pub(crate) max_width: Option<f64>,
+    pub(crate) max_height: Option<f64>,
+    /// Distance of the arrow from the left (or top) edge of the floating element
+    pub(crate) arrow_offset: Option<f64>,
+}
+
+/// Space on the given side of the anchor, inside the boundary
+fn available_space(anchor: &Rect, side: Side, options: &PositionOptions) -> f64 {
+    let boundary = &options.boundary;
+    let space = match side {
+        Side::Top => anchor.y - boundary.y,
+        Side::Bottom => boundary.bottom() - anchor.bottom(),
+        Side::Left => anchor.x - boundary.x,
+        Side::Right => boundary.right() - anchor.right(),
+    };
+    space - options.offset
+}
+
+/// Start of the floating element along the side, for the given alignment
+fn align(anchor: &Rect, side: Side, alignment: Alignment, size: f64) -> f64 {
+    let (start, anchor_size) = anchor.cross_axis(side);
+    match alignment {
+        Alignment::Start => start,
+        Alignment::Center => start + (anchor_size - size) / 2.0,
+        Alignment::End => start + anchor_size - size,
+    }
+}
+
+/// Places a floating element of the given size next to the anchor
+pub(crate) fn compute_position(
+    anchor: Rect,
+    width: f64,
+    height: f64,
+    options: &PositionOptions,
+) -> Position {
+    let main_size = |side: Side| if side.is_vertical() { height } else { width };
+
+    // Flip
+    let side = if options.fallback_sides.is_empty() {
+        options.side
+    } else {
+        let candidates: Vec<Side> = std::iter::once(options.side)
+            .chain(options.fallback_sides.iter().copied())
+            .collect();
+        candidates
+            .iter()
+            .copied()
+            .find(|side| available_space(&anchor, *side, options) >= main_size(*side))
+            .unwrap_or_else(|| {
+                candidates
+                    .iter()
+                    .copied()
+                    .max_by(|a, b| {
+                        (available_space(&anchor, *a, options) - main_size(*a))
+                            .total_cmp(&(available_space(&anchor, *b, options) - main_size(*b)))
+                    })
+                    .unwrap()
+            })
+    };
+    let (boundary_start, boundary_size) = options.boundary.cross_axis(side);
+    let boundary_end = boundary_start + boundary_size;
+
+    // Size constrain
+    let mut max_width = None;
+    let mut max_height = None;
+    let (mut width, mut height) = (width, height);
+    if options.constrain_size {
+        let available = available_space(&anchor, side, options).max(0.0);
+        if side.is_vertical() {
+            if height > available {
+                height = available;
+                max_height = Some(available);
+            }
+            if width > boundary_size {
+                width = boundary_size;
+                max_width = Some(boundary_size);
+            }
+        } else {
+            if width > available {
+                width = available;
+                max_width = Some(available);
+            }
+            if height > boundary_size {
+                height = boundary_size;
+                max_height = Some(boundary_size);
+            }
+        }
+    }
+    let cross_size = if side.is_vertical() { width } else { height };
+
+    // Alignment, swapped when it overflows and the other one does not
+    let mut alignment = options.alignment;
+    let mut cross = align(&anchor, side, alignment, cross_size);
+    let overflows = |cross: f64| cross < boundary_start || cross + cross_size > boundary_end;
+    if !options.fallback_sides.is_empty() && overflows(cross) {
+        let flipped = align(&anchor, side, alignment.opposite(), cross_size);
+        if !overflows(flipped) {
+            alignment = alignment.opposite();
+            cross = flipped;
+        }
+    }
+
+    let mut main = match side {
+        Side::Top => anchor.y - options.offset - height,
+        Side::Bottom => anchor.bottom() + options.offset,
+        Side::Left => anchor.x - options.offset - width,
+        Side::Right => anchor.right() + options.offset,
+    };
+
+    // Shift
+    if options.shift {
+        cross = cross.min(boundary_end - cross_size).max(boundary_start);
+        let (main_start, main_boundary, size) = if side.is_vertical() {
+            (options.boundary.y, options.boundary.height, height)
+        } else {
+            (options.boundary.x, options.boundary.width, width)
+        };
+        main = main.min(main_start + main_boundary - size).max(main_start);
+    }
+
+    // Arrow
+    let arrow_offset = options.arrow_padding.map(|padding| {
+        let (anchor_start, anchor_size) = anchor.cross_axis(side);
+        (anchor_start + anchor_size / 2.0 - cross)
+            .min(cross_size - padding)
+            .max(padding)
+    });
+
+    let (x, y) = if side.is_vertical() {
+        (cross, main)
+    } else {
+        (main, cross)
+    };
+    Position {
+        x,
+        y,
+        side,
+        alignment,
+        max_width,
+        max_height,
+        arrow_offset,
+    }
+}
+
+/// Moves a floating element placed by the caller (Eg:- over the anchor) inside the boundary.
+/// Height is constrained when it is taller than the boundary.
+#[cfg_attr(not(feature = "element-x-menu"), allow(dead_code))]
+pub(crate) fn contain(floating: Rect, boundary: &Rect) -> (f64, f64, Option<f64>) {
+    let x = floating
+        .x
+        .min(boundary.right() - floating.width)
+        .max(boundary.x);
+    let y = floating
+        .y
+        .min(boundary.bottom() - floating.height)
+        .max(boundary.y);
+    let max_height = (floating.height > boundary.height).then_some(boundary.height);
+    (x, y, max_height)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn boundary() -> Rect {
+        Rect::new(0.0, 0.0, 100.0, 100.0)
+    }
+
+    #[test]
+    fn places_below_with_offset() {
+        let anchor = Rect::new(40.0, 20.0, 20.0, 10.0);
+        let options = PositionOptions::new(Side::Bottom, Alignment::Center, boundary()).offset(8.0);
+        let position = compute_position(anchor, 30.0, 20.0, &options);
+        assert_eq!((position.x, position.y), (35.0, 38.0));
+        assert_eq!(position.side, Side::Bottom);
+    }
+
+    #[test]
+    fn flips_to_the_side_with_space() {
+        let anchor = Rect::new(40.0, 80.0, 20.0, 10.0);
+        let options = PositionOptions::new(Side::Bottom, Alignment::Center, boundary());
+
+        let position = compute_position(anchor, 30.0, 20.0, &options);
+        assert_eq!((position.side, position.y), (Side::Bottom, 90.0));
+
+        let position = compute_position(anchor, 30.0, 20.0, &options.flip(&[Side::Top]));
+        assert_eq!((position.side, position.y), (Side::Top, 60.0));
+    }
+
+    #[test]
+    fn flips_to_the_side_with_most_space_when_nothing_fits() {
+        let anchor = Rect::new(40.0, 30.0, 20.0, 20.0);
+        let options =
+            PositionOptions::new(Side::Top, Alignment::Center, boundary()).flip(&[Side::Bottom]);
+        let position = compute_position(anchor, 30.0, 60.0, &options);
+        assert_eq!(position.side, Side::Bottom);
+    }
+
+    #[test]
+    fn flips_the_alignment_when_it_overflows() {
+        let anchor = Rect::new(80.0, 10.0, 10.0, 10.0);
+        let options =
+            PositionOptions::new(Side::Bottom, Alignment::Start, boundary()).flip(&[Side::Top]);
+        let position = compute_position(anchor, 40.0, 20.0, &options);
+        assert_eq!((position.alignment, position.x), (Alignment::End, 50.0));
+    }
+
+    #[test]
+    fn shifts_inside_the_boundary() {
+        let anchor = Rect::new(90.0, 10.0, 10.0, 10.0);
+        let options = PositionOptions::new(Side::Bottom, Alignment::Center, boundary());
+        assert_eq!(compute_position(anchor, 40.0, 20.0, &options).x, 75.0);
+        assert_eq!(compute_position(anchor, 40.0, 20.0, &options.clone().shift()).x, 60.0);
+
+        let anchor = Rect::new(40.0, 85.0, 10.0, 10.0);
+        let options = PositionOptions::new(Side::Bottom, Alignment::Center, boundary()).shift();
+        assert_eq!(compute_position(anchor, 20.0, 20.0, &options).y, 80.0);
+    }
+
+    #[test]
+    fn constrains_the_size_to_the_available_space() {
+        let anchor = Rect::new(0.0, 70.0, 10.0, 10.0);
+        let options =
+            PositionOptions::new(Side::Bottom, Alignment::Start, boundary()).constrain_size();
+        let position = compute_position(anchor, 150.0, 50.0, &options);
+        assert_eq!(position.max_height, Some(20.0));
+        assert_eq!(position.max_width, Some(100.0));
+
+        let position = compute_position(anchor, 50.0, 10.0, &options);
+        assert_eq!((position.max_width, position.max_height), (None, None));
+    }
+
+    #[test]
+    fn points_the_arrow_to_the_anchor() {
+        let anchor = Rect::new(40.0, 10.0, 20.0, 10.0);
+        let options =
+            PositionOptions::new(Side::Bottom, Alignment::Center, boundary()).arrow(8.0);
+        assert_eq!(compute_position(anchor, 30.0, 20.0, &options).arrow_offset, Some(15.0));
+    }
+
+    #[test]
+    fn clamps_the_arrow_away_from_the_corners() {
+        let options = PositionOptions::new(Side::Bottom, Alignment::Center, boundary())
+            .shift()
+            .arrow(8.0);
+
+        let anchor = Rect::new(0.0, 10.0, 10.0, 10.0);
+        let position = compute_position(anchor, 40.0, 20.0, &options);
+        assert_eq!((position.x, position.arrow_offset), (0.0, Some(8.0)));
+
+        let anchor = Rect::new(90.0, 10.0, 10.0, 10.0);
+        let position = compute_position(anchor, 40.0, 20.0, &options);
+        assert_eq!((position.x, position.arrow_offset), (60.0, Some(32.0)));
+    }
+
+    #[test]
+    fn contain_moves_inside_the_boundary() {
+        let floating = Rect::new(90.0, -5.0, 20.0, 20.0);
+        assert_eq!(contain(floating, &boundary()), (80.0, 0.0, None));
+
+        let floating = Rect::new(10.0, 10.0, 20.0, 20.0);
+        assert_eq!(contain(floating, &boundary()), (10.0, 10.0, None));
+    }
+
+    #[test]
+    fn contain_constrains_the_height() {
+        let floating = Rect::new(0.0, 10.0, 10.0, 150.0);
+        assert_eq!(contain(floating, &boundary()), (0.0, 0.0, Some(100.0)));
+    }
+}
@@ -0,0 +1,235 @@
+//! Ripple effect shared by the components which are styled with `--trigger-effect: ripple`
+//! (Eg:- `XMenuItem` and `XButton`).
+//!
+//! The owner keeps a `Ripples` value in its state, forwards `RippleMessage`s back to it and
+//! renders `Ripples::view` inside its root element. Durations and easings are given by the owner
+//! as `Transition`s, so each component can expose them as props.
+use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::{spawn_local, JsFuture};
+use web_sys::{window, Element, MouseEvent};
+use yew::{html, Callback, NodeRef};
+
+use crate::{
+    utils::{new_animation, property_keyframes},
+    Transition,
+};
+
+/// Messages of the ripple animations. Owner should pass them to `Ripples::update`.
+#[doc(hidden)]
+pub enum RippleMessage {
+    GrowFinished(u32),
+    FadeFinished(u32),
+}
+
+#[derive(PartialEq, Clone, Copy)]
+enum RippleStatus {
+    /// Waiting for the element to be rendered
+    Created,
+    Growing,
+    Grown,
+    Fading,
+}
+
+#[derive(PartialEq, Clone, Copy)]
+enum RippleKind {
+    /// Kept until the pointer is released
+    PointerDown,
+    /// Fades out as soon as it is grown
+    Click,
+}
+
+struct Ripple {
+    id: u32,
+    node_ref: NodeRef,
+    size: f64,
+    top: f64,
+    left: f64,
+    kind: RippleKind,
+    status: RippleStatus,
+}
+
+pub(crate) struct Ripples {
+    node_ref: NodeRef,
+    ripples: Vec<Ripple>,
+    next_id: u32,
+    /// Pointer is still down
+    held: bool,
+    on_message: Callback<RippleMessage>,
+}
+
+impl Ripples {
+    pub(crate) fn new(on_message: Callback<RippleMessage>) -> Self {
+        Self {
+            node_ref: NodeRef::default(),
+            ripples: Vec::new(),
+            next_id: 0,
+            held: false,
+            on_message,
+        }
+    }
+
+    /// Replaces the callback receiving the animation messages. Used by function components,
+    /// which are creating their callbacks on each render.
+    #[cfg(feature = "element-x-button")]
+    pub(crate) fn set_on_message(&mut self, on_message: Callback<RippleMessage>) {
+        self.on_message = on_message;
+    }
+
+    /// There is no ripple running
+    #[cfg(feature = "element-x-menuitem")]
+    pub(crate) fn is_empty(&self) -> bool {
+        self.ripples.is_empty()
+    }
+
+    /// Spawns a ripple at the pointer which is kept until `release` is called
+    pub(crate) fn press(&mut self, e: &MouseEvent) {
+        self.held = true;
+        self.spawn(e, RippleKind::PointerDown);
+    }
+
+    /// Spawns a ripple which fades out as soon as it is grown. Ripple is started from the center
+    /// when the click is made with the keyboard.
+    pub(crate) fn click(&mut self, e: &MouseEvent) {
+        self.spawn(e, RippleKind::Click);
+    }
+
+    /// Fades out the ripples which are kept by the pointer
+    pub(crate) fn release(&mut self, fade_transition: &Transition) {
+        self.held = false;
+        for i in 0..self.ripples.len() {
+            if self.ripples[i].status == RippleStatus::Grown {
+                self.fade(i, fade_transition);
+            }
+        }
+    }
+
+    /// Returns `true` when the ripples should be re-rendered
+    pub(crate) fn update(&mut self, msg: RippleMessage, fade_transition: &Transition) -> bool {
+        match msg {
+            RippleMessage::GrowFinished(id) => {
+                if let Some(i) = self.ripples.iter().position(|r| r.id == id) {
+                    self.ripples[i].status = RippleStatus::Grown;
+                    if !self.held || self.ripples[i].kind == RippleKind::Click {
+                        self.fade(i, fade_transition);
+                    }
+                }
+                false
+            }
+            RippleMessage::FadeFinished(id) => {
+                self.ripples.retain(|r| r.id != id);
+                true
+            }
+        }
+    }
+
+    /// Starts growing the newly rendered ripples. Call it from `Component::rendered`.
+    pub(crate) fn rendered(&mut self, grow_transition: &Transition) {
+        for ripple in self.ripples.iter_mut() {
+            if ripple.status != RippleStatus::Created {
+                continue;
+            }
+            if let Some(element) = ripple.node_ref.cast::<Element>() {
+                ripple.status = RippleStatus::Growing;
+                let animation = new_animation(
+                    &element,
+                    &property_keyframes("transform", &["scale3d(0, 0, 0)", "none"]),
+                    grow_transition.duration,
+                    &grow_transition.easing,
+                );
+                let id = ripple.id;
+                let on_message = self.on_message.clone();
+                spawn_local(async move {
+                    JsFuture::from(animation.finished().unwrap()).await.ok();
+                    on_message.emit(RippleMessage::GrowFinished(id));
+                });
+            }
+        }
+    }
+
+    /// Container of the ripples. Classes are given by the owner to match its stylesheet.
+    pub(crate) fn view(&self, class: &'static str, ripple_class: &'static str) -> yew::Html {
+        html! {
+            <div ref={self.node_ref.clone()} {class}>
+                {for self.ripples.iter().map(|ripple| {
+                    let class = match ripple.kind {
+                        RippleKind::Click => format!("{} click-ripple", ripple_class),
+                        RippleKind::PointerDown => format!("{} pointer-down-ripple", ripple_class),
+                    };
+                    let style = format!(
+                        "width: {}px; height: {}px; top: {}px; left: {}px",
+                        ripple.size, ripple.size, ripple.top, ripple.left
+                    );
+                    html! {
+                        <div key={ripple.id} ref={ripple.node_ref.clone()} {class} {style}></div>
+                    }
+                })}
+            </div>
+        }
+    }
+
+    fn spawn(&mut self, e: &MouseEvent, kind: RippleKind) {
+        let container = match self.node_ref.cast::<Element>() {
+            Some(container) => container,
+            None => return,
+        };
+        let rect = container.get_bounding_client_rect();
+        let size = rect.width().max(rect.height()) * 1.5;
+
+        // Clicks made with the keyboard have no coordinates
+        let keyboard = e.detail() == 0 && e.client_x() == 0 && e.client_y() == 0;
+        let (x, y) = if keyboard {
+            (rect.width() / 2.0, rect.height() / 2.0)
+        } else {
+            (
+                e.client_x() as f64 - rect.x(),
+                e.client_y() as f64 - rect.y(),
+            )
+        };
+
+        self.ripples.push(Ripple {
+            id: self.next_id,
+            node_ref: NodeRef::default(),
+            size,
+            top: y - size / 2.0,
+            left: x - size / 2.0,
+            kind,
+            status: RippleStatus::Created,
+        });
+        self.next_id += 1;
+    }
+
+    fn fade(&mut self, i: usize, fade_transition: &Transition) {
+        let ripple = &mut self.ripples[i];
+        let element = match ripple.node_ref.cast::<Element>() {
+            Some(element) => element,
+            None => return,
+        };
+
+        // Fade from the current opacity, which is given by the theme
+        let opacity = window()
+            .unwrap()
+            .get_computed_style(&element)
+            .ok()
+            .flatten()
+            .and_then(|style| style.get_property_value("opacity").ok())
+            .unwrap_or_else(|| String::from("1"));
+
+        ripple.status = RippleStatus::Fading;
+        let animation = new_animation(
+            &element,
+            &property_keyframes("opacity", &[&opacity, "0"]),
+            fade_transition.duration,
+            &fade_transition.easing,
+        );
+        let id = ripple.id;
+        let on_message = self.on_message.clone();
+        spawn_local(async move {
+            JsFuture::from(animation.finished().unwrap()).await.ok();
+            // Keep the faded ripple invisible until it is removed
+            element
+                .dyn_ref::<web_sys::HtmlElement>()
+                .map(|element| element.style().set_property("opacity", "0"));
+            on_message.emit(RippleMessage::FadeFinished(id));
+        });
+    }
+}
@@ -3,7 +3,13 @@ pub(crate) fn console_log(message: String) {
 }
 
 // Animations
-#[cfg(any(all(feature = "element-x-tooltip", feature = "element-x-button"), feature="element-x-menuitem"))]
+#[cfg(any(
+    feature = "element-x-button",
+    feature = "element-x-menuitem",
+    feature = "element-x-menu",
+    feature = "element-x-tooltip",
+    feature = "element-x-switch"
+))]
 pub(crate) fn new_animation(
     target: &web_sys::Element,
     keyframes: &js_sys::Object,
@@ -25,6 +31,92 @@ pub(crate) fn new_animation(
     animation
 }
 
+/// Minimum time (in milliseconds) to keep an element in pressed state. Touch presses are kept
+/// longer and also count as long presses after this time.
+#[cfg(any(
+    feature = "element-x-menuitem",
+    feature = "element-x-contextmenu",
+    feature = "element-x-tooltip"
+))]
+pub(crate) fn min_pressed_time(pointer_type: &str) -> u64 {
+    if pointer_type == "touch" {
+        600
+    } else {
+        150
+    }
+}
+
+#[cfg(any(
+    feature = "element-x-tooltip",
+    feature = "element-x-label",
+    feature = "element-x-numberinput"
+))]
+thread_local! {
+    /// Used to generate the element ids
+    static NEXT_ID: std::cell::Cell<u32> = const { std::cell::Cell::new(0) };
+}
+
+/// Generates an unique element id, starting with the prefix (Eg:- `"x-label-0"`)
+#[cfg(any(
+    feature = "element-x-tooltip",
+    feature = "element-x-label",
+    feature = "element-x-numberinput"
+))]
+pub(crate) fn next_id(prefix: &str) -> String {
+    let id = NEXT_ID.with(|next_id| {
+        let id = next_id.get();
+        next_id.set(id + 1);
+        id
+    });
+    format!("{}-{}", prefix, id)
+}
+
+/// Distance (in pixels) which the floating elements keep from the edges of the window
+#[cfg(any(feature = "element-x-tooltip", feature = "element-x-menu"))]
+const WINDOW_WHITESPACE: f64 = 8.0;
+
+/// The window, less the whitespace on every edge. Floating elements are kept inside this.
+#[cfg(any(feature = "element-x-tooltip", feature = "element-x-menu"))]
+pub(crate) fn window_rect() -> crate::positioning::Rect {
+    let win = web_sys::window().unwrap();
+    let width = win.inner_width().unwrap().as_f64().unwrap();
+    let height = win.inner_height().unwrap().as_f64().unwrap();
+    crate::positioning::Rect::new(
+        WINDOW_WHITESPACE,
+        WINDOW_WHITESPACE,
+        (width - WINDOW_WHITESPACE * 2.0).max(0.0),
+        (height - WINDOW_WHITESPACE * 2.0).max(0.0),
+    )
+}
+
+/// Bounding rectangle of the element in client coordinates
+#[cfg(any(feature = "element-x-tooltip", feature = "element-x-menu"))]
+pub(crate) fn client_rect(element: &web_sys::Element) -> crate::positioning::Rect {
+    let rect = element.get_bounding_client_rect();
+    crate::positioning::Rect::new(rect.x(), rect.y(), rect.width(), rect.height())
+}
+
+/// Keyframes animating a single CSS property through the given values. Keyframes must be a
+/// plain object, so they are not created with `serde_wasm_bindgen` (which gives a `Map`).
+#[cfg(any(
+    feature = "element-x-button",
+    feature = "element-x-menuitem",
+    feature = "element-x-menu",
+    feature = "element-x-tooltip",
+    feature = "element-x-switch"
+))]
+pub(crate) fn property_keyframes(property: &str, values: &[&str]) -> js_sys::Object {
+    use wasm_bindgen::JsValue;
+
+    let keyframes = js_sys::Object::new();
+    let values = values
+        .iter()
+        .map(|value| JsValue::from_str(value))
+        .collect::<js_sys::Array>();
+    js_sys::Reflect::set(&keyframes, &JsValue::from_str(property), &values).unwrap();
+    keyframes
+}
+
 #[cfg(feature = "feature-intl")]
 pub(crate) async fn load_text_content(url: web_sys::Url) -> Result<String, wasm_bindgen::JsValue> {
     use wasm_bindgen::JsCast;
@@ -45,3 +137,140 @@ pub(crate) async fn load_text_content(url: web_sys::Url) -> Result<String, wasm_
 
     Ok(text.as_string().unwrap())
 }
+
+/// Listener attached to a DOM event target (Eg:- the window). The listener is removed when this
+/// value is dropped, so keep it inside the component state.
+#[cfg(any(
+    feature = "element-x-menubar",
+    feature = "element-x-contextmenu",
+    feature = "element-x-tooltip",
+    feature = "element-x-numberinput"
+))]
+pub(crate) struct EventListener {
+    target: web_sys::EventTarget,
+    event_type: &'static str,
+    capture: bool,
+    callback: wasm_bindgen::closure::Closure<dyn FnMut(web_sys::Event)>,
+}
+
+#[cfg(any(
+    feature = "element-x-menubar",
+    feature = "element-x-contextmenu",
+    feature = "element-x-tooltip",
+    feature = "element-x-numberinput"
+))]
+impl EventListener {
+    pub(crate) fn new<F>(target: &web_sys::EventTarget, event_type: &'static str, callback: F) -> Self
+    where
+        F: FnMut(web_sys::Event) + 'static,
+    {
+        Self::with_capture(target, event_type, false, callback)
+    }
+
+    /// Listens in the capture phase when `capture` is `true`. Useful for the events which are
+    /// not bubbling (Eg:- scrolling of any element).
+    pub(crate) fn with_capture<F>(
+        target: &web_sys::EventTarget,
+        event_type: &'static str,
+        capture: bool,
+        callback: F,
+    ) -> Self
+    where
+        F: FnMut(web_sys::Event) + 'static,
+    {
+        use wasm_bindgen::JsCast;
+
+        let callback = wasm_bindgen::closure::Closure::wrap(
+            Box::new(callback) as Box<dyn FnMut(web_sys::Event)>
+        );
+        target
+            .add_event_listener_with_callback_and_bool(
+                event_type,
+                callback.as_ref().unchecked_ref(),
+                capture,
+            )
+            .unwrap();
+
+        Self {
+            target: target.clone(),
+            event_type,
+            capture,
+            callback,
+        }
+    }
+}
+
+#[cfg(any(
+    feature = "element-x-menubar",
+    feature = "element-x-contextmenu",
+    feature = "element-x-tooltip",
+    feature = "element-x-numberinput"
+))]
+impl Drop for EventListener {
+    fn drop(&mut self) {
+        use wasm_bindgen::JsCast;
+
+        self.target
+            .remove_event_listener_with_callback_and_bool(
+                self.event_type,
+                self.callback.as_ref().unchecked_ref(),
+                self.capture,
+            )
+            .ok();
+    }
+}
+
+// `ResizeObserver` of `web_sys` is still behind `web_sys_unstable_apis`
+#[cfg(feature = "element-x-tooltip")]
+mod resize_observer {
+    use wasm_bindgen::prelude::wasm_bindgen;
+
+    #[wasm_bindgen]
+    extern "C" {
+        pub(crate) type ResizeObserver;
+
+        #[wasm_bindgen(constructor)]
+        pub(crate) fn new(callback: &js_sys::Function) -> ResizeObserver;
+
+        #[wasm_bindgen(method)]
+        pub(crate) fn observe(this: &ResizeObserver, target: &web_sys::Element);
+
+        #[wasm_bindgen(method)]
+        pub(crate) fn disconnect(this: &ResizeObserver);
+    }
+}
+
+/// Calls the callback whenever the observed element is resized. Observer is disconnected when
+/// this value is dropped.
+#[cfg(feature = "element-x-tooltip")]
+pub(crate) struct ResizeListener {
+    observer: resize_observer::ResizeObserver,
+    _callback: wasm_bindgen::closure::Closure<dyn FnMut()>,
+}
+
+#[cfg(feature = "element-x-tooltip")]
+impl ResizeListener {
+    pub(crate) fn new<F>(target: &web_sys::Element, callback: F) -> Self
+    where
+        F: FnMut() + 'static,
+    {
+        use wasm_bindgen::JsCast;
+
+        let callback =
+            wasm_bindgen::closure::Closure::wrap(Box::new(callback) as Box<dyn FnMut()>);
+        let observer = resize_observer::ResizeObserver::new(callback.as_ref().unchecked_ref());
+        observer.observe(target);
+
+        Self {
+            observer,
+            _callback: callback,
+        }
+    }
+}
+
+#[cfg(feature = "element-x-tooltip")]
+impl Drop for ResizeListener {
+    fn drop(&mut self) {
+        self.observer.disconnect();
+    }
+}
@@ -1,15 +1,18 @@
 use std::fmt::Write;
-use web_sys::MouseEvent;
+use std::rc::Rc;
+use wasm_bindgen::JsCast;
+use web_sys::{Element, FocusEvent, HtmlElement, KeyboardEvent, MouseEvent, Node, PointerEvent};
 use yew::html::ChildrenRenderer;
 use yew::{
-    function_component, use_context, use_reducer, use_state, Callback, Reducible,
-    UseReducerHandle,
+    function_component, use_context, use_effect, use_effect_with_deps, use_force_update,
+    use_mut_ref, use_node_ref, use_state, Callback,
 };
-use yew::{html, ContextProvider, Properties};
+use yew::{html, Properties};
 
 use crate::calculate_computed_size;
+use crate::ripples::Ripples;
 use crate::xcontainer::XContainerContext;
-use crate::XComponentSize;
+use crate::{CSSEasing, Transition, XComponentSize};
 
 #[derive(PartialEq, Clone)]
 pub enum XButtonSkin {
@@ -40,15 +43,44 @@ impl ToString for XButtonSkin {
     }
 }
 
+/// Visual effect shown when the button is pressed
+#[derive(PartialEq, Clone, Default)]
+pub enum XButtonTriggerEffect {
+    /// Ripple spawned at the pointer, kept while the pointer is held. Used by the Material-like
+    /// skins.
+    Ripple,
+    #[default]
+    None,
+}
+
 #[derive(PartialEq, Clone, derive_more::From)]
 pub enum XButtonChild {
     #[cfg(feature = "element-x-tooltip")]
     Tooltip(yew::virtual_dom::VChild<crate::xtooltip::XTooltip>),
     #[cfg(feature = "element-x-label")]
     Label(yew::virtual_dom::VChild<crate::xlabel::XLabel>),
+    /// Menu opened as a dropdown below the button
+    #[cfg(feature = "element-x-menu")]
+    Menu(yew::virtual_dom::VChild<crate::xmenu::XMenu>),
+    /// Popover opened as a dropdown below the button
+    #[cfg(feature = "element-x-popover")]
+    Popover(yew::virtual_dom::VChild<crate::xpopover::XPopover>),
     Other(yew::Html),
 }
 
+impl XButtonChild {
+    /// Value of `aria-haspopup` when this child is opened by the button
+    fn popup_type(&self) -> Option<&'static str> {
+        match self {
+            #[cfg(feature = "element-x-menu")]
+            Self::Menu(_) => Some("menu"),
+            #[cfg(feature = "element-x-popover")]
+            Self::Popover(_) => Some("dialog"),
+            _ => None,
+        }
+    }
+}
+
 impl Into<yew::Html> for XButtonChild {
     fn into(self) -> yew::Html {
         match self {
@@ -56,6 +88,10 @@ impl Into<yew::Html> for XButtonChild {
             Self::Tooltip(child) => child.into(),
             #[cfg(feature = "element-x-label")]
             Self::Label(child) => child.into(),
+            #[cfg(feature = "element-x-menu")]
+            Self::Menu(child) => child.into(),
+            #[cfg(feature = "element-x-popover")]
+            Self::Popover(child) => child.into(),
             Self::Other(child) => child.into(),
         }
     }
@@ -64,19 +100,25 @@ impl Into<yew::Html> for XButtonChild {
 /// All props associated with the XButton component
 #[derive(PartialEq, Properties)]
 pub struct XButtonProps {
-    /// A unique value associated with this button.
+    /// A unique value associated with this button. Used by `XButtons` to track the toggled
+    /// buttons.
     #[prop_or_default]
     pub value: Option<String>,
-    /// Whether this button is toggled.
+    /// Whether this button is toggled. When given, the owner controls the state and should
+    /// update it in `ontoggle`. Otherwise the button keeps its own state.
+    #[prop_or_default]
+    pub toggled: Option<bool>,
+    /// Initial toggled state when `toggled` is not given.
     #[prop_or_default]
-    pub toggled: bool,
+    pub default_toggled: bool,
     /// Whether this button can be toggled on/off by the user (e.g. by clicking the button).
     #[prop_or_default]
     pub togglable: bool,
-    /// Whether the this button has "mixed" state.
+    /// Whether the this button has "mixed" state. Toggling a mixed button turns it on, owner
+    /// should clear this prop in `ontoggle`.
     #[prop_or_default]
     pub mixed: bool,
-    /// Whether this button is disabled.
+    /// Whether this button is disabled. Disabled buttons are not firing any callback.
     #[prop_or_default]
     pub disabled: bool,
     /// Whether the button should take less horizontal space.
@@ -89,59 +131,77 @@ pub struct XButtonProps {
     #[prop_or_default]
     pub size: Option<XComponentSize>,
 
+    #[prop_or_default]
+    pub trigger_effect: XButtonTriggerEffect,
+    /// Animation of the ripples. Only the duration and the easing are used.
+    #[prop_or(Transition::new("transform", 300.0, CSSEasing::CubicBezier(0.4, 0.0, 0.2, 1.0)))]
+    pub ripple_grow_transition: Transition,
+    /// Animation to fade out the ripples. Only the duration and the easing are used.
+    #[prop_or(Transition::new("opacity", 300.0, CSSEasing::CubicBezier(0.4, 0.0, 0.2, 1.0)))]
+    pub ripple_fade_transition: Transition,
+
     #[prop_or_default]
     pub children: ChildrenRenderer<XButtonChild>,
 
     #[prop_or_default]
     pub onclick: Option<Callback<MouseEvent>>,
 
+    /// This callback is firing on the click event when the button is togglable, with the new
+    /// toggled state.
     #[prop_or_default]
-    pub ontoggle: Option<Callback<MouseEvent>>,
-}
-
-#[derive(PartialEq)]
-pub enum XButtonEvent {
-    Click(MouseEvent),
-    MouseEnter(MouseEvent),
-    MouseLeave(MouseEvent),
-}
-
-#[derive(PartialEq)]
-pub struct XButtonMessage {
-    pub event: Option<XButtonEvent>,
+    pub ontoggle: Option<Callback<(MouseEvent, bool)>>,
 }
 
-impl Reducible for XButtonMessage {
-    type Action = Option<XButtonEvent>;
-
-    fn reduce(self: std::rc::Rc<Self>, action: Self::Action) -> std::rc::Rc<Self> {
-        XButtonMessage { event: action }.into()
-    }
-}
-
-pub(crate) type XButtonContext = UseReducerHandle<XButtonMessage>;
-
 /// `XButton` has the same purpose as the standard HTML `button` element, but can be easily
 /// composited with other YewXel elements.
 #[function_component]
 pub fn XButton(props: &XButtonProps) -> yew::Html {
     let pressed = use_state(|| false);
+    let root_ref = use_node_ref();
+    let focus_visible = use_state(|| false);
+    // Pointer is down on the button, so the focus is not coming from the keyboard
+    let pointer_down = use_mut_ref(|| false);
+
+    // State of the uncontrolled buttons
+    let own_toggled = use_state(|| props.default_toggled);
+
+    // Buttons of a tracking group are toggled by the group
+    #[cfg(feature = "element-x-buttons")]
+    let group = use_context::<crate::xbuttons::XButtonsContext>()
+        .filter(|group| group.tracking != crate::xbuttons::XButtonsTracking::None)
+        .zip(props.value.clone());
+    #[cfg(feature = "element-x-buttons")]
+    let toggled = match &group {
+        Some((group, value)) => group.is_toggled(value),
+        None => props.toggled.unwrap_or(*own_toggled),
+    };
+    #[cfg(feature = "element-x-buttons")]
+    let togglable = props.togglable || group.is_some();
 
-    for _child in props.children.iter() {
-        // TODO: Check for menu and popovers 
-    }
+    #[cfg(not(feature = "element-x-buttons"))]
+    let toggled = props.toggled.unwrap_or(*own_toggled);
+    #[cfg(not(feature = "element-x-buttons"))]
+    let togglable = props.togglable;
+
+    // Menu or popover opened by this button, and whether to focus its contents after opening
+    let expanded = use_state(|| None::<bool>);
+    let popup_type = props.children.iter().find_map(|child| child.popup_type());
 
     let mut classes = String::from("x-button");
 
-    if props.toggled {
+    if expanded.is_some() {
+        classes.push_str(" expanded");
+    }
+
+    if toggled {
         classes.push_str(" toggled");
     }
 
-    if props.disabled {
-        classes.push_str(" disabled");
+    if props.mixed {
+        classes.push_str(" mixed");
     }
 
-    if props.togglable {
+    if togglable {
         classes.push_str(" togglable");
     }
 
@@ -153,10 +213,14 @@ pub fn XButton(props: &XButtonProps) -> yew::Html {
         classes.push_str(" condensed");
     }
 
-    if *pressed.clone() {
+    if *pressed {
         classes.push_str(" pressed");
     }
 
+    if *focus_visible {
+        classes.push_str(" focus-visible");
+    }
+
     write!(classes, " skin-{}", props.skin.to_string()).unwrap();
 
     if let Some(size) = props.size.clone() {
@@ -169,68 +233,341 @@ pub fn XButton(props: &XButtonProps) -> yew::Html {
     let computed_size = calculate_computed_size(props.size.clone(), context.size.clone());
     write!(classes, " computedsize-{}", computed_size.to_string()).unwrap();
 
-    let message = use_reducer(|| XButtonMessage { event: None });
+    let force_update = use_force_update();
+    let ripples = use_mut_ref(|| Ripples::new(Callback::noop()));
+    {
+        // Ripples are storing the callback, so it only holds a weak reference to them
+        let weak_ripples = Rc::downgrade(&ripples);
+        let force_update = force_update.clone();
+        use_effect_with_deps(
+            move |fade_transition| {
+                let fade_transition = fade_transition.clone();
+                if let Some(ripples) = weak_ripples.upgrade() {
+                    ripples
+                        .borrow_mut()
+                        .set_on_message(Callback::from(move |msg| {
+                            let ripples = match weak_ripples.upgrade() {
+                                Some(ripples) => ripples,
+                                None => return,
+                            };
+                            if ripples.borrow_mut().update(msg, &fade_transition) {
+                                force_update.force_update();
+                            }
+                        }));
+                }
+                || ()
+            },
+            props.ripple_fade_transition.clone(),
+        );
+    }
+    {
+        let ripples = ripples.clone();
+        let grow_transition = props.ripple_grow_transition.clone();
+        use_effect(move || {
+            ripples.borrow_mut().rendered(&grow_transition);
+        });
+    }
+    let ripple = props.trigger_effect == XButtonTriggerEffect::Ripple && !props.disabled;
 
     let onclick = {
-        let message = message.clone();
         let oc_callback = props.onclick.clone();
         let ot_callback = props.ontoggle.clone();
-        let togglable = props.togglable;
+        #[cfg(feature = "element-x-buttons")]
+        let group = group.clone();
+        let disabled = props.disabled;
+        let mixed = props.mixed;
+        let controlled = props.toggled.is_some();
+        let own_toggled = own_toggled.clone();
+        let ripples = ripples.clone();
+        let force_update = force_update.clone();
+        let root_ref = root_ref.clone();
+        let expanded = expanded.clone();
+        let has_popup = popup_type.is_some();
         Callback::from(move |e: MouseEvent| {
+            // Ignore the clicks bubbling from the opened menu or popover
+            if disabled || in_popup(&root_ref, &e) {
+                return;
+            }
+
+            // Clicks made with the keyboard are not preceded by a pointer down
+            if ripple && e.detail() == 0 {
+                ripples.borrow_mut().click(&e);
+                force_update.force_update();
+            }
+
+            if has_popup {
+                expanded.set(match *expanded {
+                    Some(_) => None,
+                    None => Some(false),
+                });
+                return;
+            }
+
+            #[cfg(feature = "element-x-buttons")]
+            if let Some((group, value)) = &group {
+                let selected = group.toggle(value);
+                let new_toggled = selected.contains(value);
+                if new_toggled != toggled {
+                    group.on_change.emit(selected);
+                    if let Some(callback) = &ot_callback {
+                        callback.emit((e, new_toggled));
+                    }
+                }
+                return;
+            }
+
             if togglable {
+                let new_toggled = mixed || !toggled;
+                if !controlled {
+                    own_toggled.set(new_toggled);
+                }
                 if let Some(callback) = &ot_callback {
-                    callback.emit(e);
+                    callback.emit((e, new_toggled));
                 }
             } else if let Some(callback) = &oc_callback {
-                message.dispatch(Some(XButtonEvent::Click(e.clone())));
                 callback.emit(e);
             }
         })
     };
 
-    let onmouseenter = {
-        let message = message.clone();
-        Callback::from(move |e: MouseEvent| {
-            message.dispatch(Some(XButtonEvent::MouseEnter(e)));
+    let onpointerdown = {
+        let pressed = pressed.clone();
+        let pointer_down = pointer_down.clone();
+        let ripples = ripples.clone();
+        let force_update = force_update.clone();
+        let root_ref = root_ref.clone();
+        let disabled = props.disabled;
+        Callback::from(move |e: PointerEvent| {
+            if disabled || e.button() != 0 || in_popup(&root_ref, &e) {
+                return;
+            }
+            *pointer_down.borrow_mut() = true;
+            pressed.set(true);
+
+            // Keep receiving the pointer events, so the button is released outside itself
+            if let Some(target) = e.current_target().and_then(|t| t.dyn_into::<Element>().ok()) {
+                target.set_pointer_capture(e.pointer_id()).ok();
+            }
+            if ripple {
+                ripples.borrow_mut().press(&e);
+                force_update.force_update();
+            }
         })
     };
 
-    let onmouseleave = {
-        let message = message.clone();
-        Callback::from(move |e: MouseEvent| {
-            message.dispatch(Some(XButtonEvent::MouseLeave(e)));
+    let onpointerup = {
+        let pressed = pressed.clone();
+        let pointer_down = pointer_down.clone();
+        let ripples = ripples.clone();
+        let fade_transition = props.ripple_fade_transition.clone();
+        Callback::from(move |_e: PointerEvent| {
+            *pointer_down.borrow_mut() = false;
+            if *pressed {
+                pressed.set(false);
+            }
+            ripples.borrow_mut().release(&fade_transition);
         })
     };
 
-    let onmousedown = {
+    // Enter clicks the button when pressed, Space when released. Both are opening the popup
+    // with the first item focused. ArrowDown also moves the focus into a popup which is opened
+    // with the pointer, and Escape closes it.
+    let onkeydown = {
+        let expanded = expanded.clone();
         let pressed = pressed.clone();
-        Callback::from(move |_e: MouseEvent| {
-            pressed.set(true);
+        let focus_visible = focus_visible.clone();
+        let root_ref = root_ref.clone();
+        let has_popup = popup_type.is_some();
+        let disabled = props.disabled;
+        Callback::from(move |e: KeyboardEvent| {
+            if disabled || !is_root_target(&root_ref, &e) {
+                return;
+            }
+            if !*focus_visible {
+                focus_visible.set(true);
+            }
+            match e.key().as_str() {
+                "ArrowDown" if has_popup => {
+                    e.prevent_default();
+                    if *expanded != Some(true) {
+                        expanded.set(Some(true));
+                    }
+                }
+                "Enter" | " " if has_popup => {
+                    e.prevent_default();
+                    if expanded.is_none() {
+                        expanded.set(Some(true));
+                    }
+                }
+                "Escape" if expanded.is_some() => {
+                    e.prevent_default();
+                    expanded.set(None);
+                }
+                "Enter" => {
+                    e.prevent_default();
+                    if !e.repeat() {
+                        if let Some(root) = root_ref.cast::<HtmlElement>() {
+                            root.click();
+                        }
+                    }
+                }
+                " " => {
+                    // Prevent scrolling the page
+                    e.prevent_default();
+                    if !*pressed {
+                        pressed.set(true);
+                    }
+                }
+                _ => {}
+            }
         })
     };
 
-    let onmouseup = {
+    let onkeyup = {
         let pressed = pressed.clone();
-        Callback::from(move |_e: MouseEvent| {
-            pressed.set(false);
+        let root_ref = root_ref.clone();
+        Callback::from(move |e: KeyboardEvent| {
+            if e.key() == " " && *pressed && is_root_target(&root_ref, &e) {
+                pressed.set(false);
+                if let Some(root) = root_ref.cast::<HtmlElement>() {
+                    root.click();
+                }
+            }
         })
     };
 
+    // Focus ring is only shown when the button is focused with the keyboard
+    let onfocusin = {
+        let focus_visible = focus_visible.clone();
+        let pointer_down = pointer_down.clone();
+        let root_ref = root_ref.clone();
+        Callback::from(move |e: FocusEvent| {
+            if is_root_target(&root_ref, &e) {
+                focus_visible.set(!*pointer_down.borrow());
+            }
+        })
+    };
+
+    // Close the popup when the focus leaves the button (Eg:- clicked outside)
+    let onfocusout = {
+        let expanded = expanded.clone();
+        let focus_visible = focus_visible.clone();
+        let root_ref = root_ref.clone();
+        Callback::from(move |e: FocusEvent| {
+            let inside = match (
+                root_ref.cast::<Element>(),
+                e.related_target().and_then(|t| t.dyn_into::<Node>().ok()),
+            ) {
+                (Some(root), Some(related)) => root.contains(Some(&related)),
+                _ => false,
+            };
+            if is_root_target(&root_ref, &e) && *focus_visible {
+                focus_visible.set(false);
+            }
+            if !inside && expanded.is_some() {
+                expanded.set(None);
+            }
+        })
+    };
+
+    let ripples_view = ripples.borrow().view("x-button-ripples", "x-button-ripple");
+
+    // Menus and popovers are opened below the button through the opener context
+    #[cfg(feature = "element-x-menu")]
+    let children = {
+        use crate::xmenu::{XMenuOpen, XMenuOpenerContext};
+        use yew::ContextProvider;
+
+        let on_dismiss = {
+            let expanded = expanded.clone();
+            let root_ref = root_ref.clone();
+            Callback::from(move |_reason| {
+                expanded.set(None);
+                if let Some(root) = root_ref.cast::<HtmlElement>() {
+                    root.focus().ok();
+                }
+            })
+        };
+        let opener = XMenuOpenerContext {
+            open: expanded.map(|_| XMenuOpen::BelowElement(root_ref.clone())),
+            focus_first: expanded.unwrap_or(false),
+            submenu: false,
+            on_dismiss,
+        };
+        props.children.iter().map(move |child| -> yew::Html {
+            if child.popup_type().is_none() {
+                return child.into();
+            }
+            html! {
+                <ContextProvider<XMenuOpenerContext> context={opener.clone()}>
+                    {child}
+                </ContextProvider<XMenuOpenerContext>>
+            }
+        })
+    };
+    #[cfg(not(feature = "element-x-menu"))]
+    let children = props.children.iter();
+
+    let aria_pressed = match (togglable, props.mixed, toggled) {
+        (false, _, _) => None,
+        (true, true, _) => Some("mixed"),
+        (true, false, true) => Some("true"),
+        (true, false, false) => Some("false"),
+    };
+
+    let mut arrow_classes = String::from("x-button-arrow");
+    if popup_type.is_none() {
+        arrow_classes.push_str(" hidden");
+    }
+
     html! {
-        <ContextProvider<XButtonContext> context={message}>
         <div
+            ref={root_ref}
+            role="button"
+            tabindex={if props.disabled {"-1"} else {"0"}}
+            aria-haspopup={popup_type}
+            aria-expanded={popup_type.map(|_| if expanded.is_some() {"true"} else {"false"})}
+            aria-pressed={aria_pressed}
+            aria-disabled={if props.disabled {"true"} else {"false"}}
+            {onkeydown}
+            {onkeyup}
+            {onfocusin}
+            {onfocusout}
             onclick={onclick}
-            onmouseenter={onmouseenter}
-            onmousedown={onmousedown}
-            onmouseleave={onmouseleave}
-            onmouseup={onmouseup}
+            onpointerdown={onpointerdown}
+            onpointerup={onpointerup.clone()}
+            onpointercancel={onpointerup}
             class={classes}>
-          <div class="x-button-ripples"></div>
-          {for props.children.iter()}
-          <svg class="x-button-arrow" part="arrow" viewBox="0 0 100 100" preserveAspectRatio="none">
+          {ripples_view}
+          {for children}
+          <svg class={arrow_classes} part="arrow" viewBox="0 0 100 100" preserveAspectRatio="none">
             <path class="x-button-arrow-path"></path>
           </svg>
         </div>
-        </ContextProvider<XButtonContext>>
+    }
+}
+
+/// Whether the event is coming from a menu or a popover opened by the button
+fn in_popup(root_ref: &yew::NodeRef, e: &web_sys::Event) -> bool {
+    let (root, target) = match (
+        root_ref.cast::<Element>(),
+        e.target().and_then(|t| t.dyn_into::<Element>().ok()),
+    ) {
+        (Some(root), Some(target)) => (root, target),
+        _ => return false,
+    };
+    target
+        .closest(".x-menu, .x-popover")
+        .ok()
+        .flatten()
+        .map(|popup| root.contains(Some(&popup)))
+        .unwrap_or(false)
+}
+
+/// Whether the event is targeting the button itself, rather than its contents
+fn is_root_target(root_ref: &yew::NodeRef, e: &web_sys::Event) -> bool {
+    match (root_ref.get(), e.target()) {
+        (Some(root), Some(target)) => root.is_same_node(target.dyn_ref::<Node>()),
+        _ => false,
     }
 }
@@ -0,0 +1,171 @@
+//! `XButtons` groups `XButton`s with joined borders, and optionally tracks which of them are
+//! toggled using their `value`.
+//!
+//! ```
+//! <XButtons tracking={XButtonsTracking::AtLeastOne} default_value={vec![String::from("left")]}>
+//!     <XButton value="left"><XLabel>{"Left"}</XLabel></XButton>
+//!     <XButton value="center"><XLabel>{"Center"}</XLabel></XButton>
+//!     <XButton value="right"><XLabel>{"Right"}</XLabel></XButton>
+//! </XButtons>
+//! ```
+//!
+//! > Enable `"element-x-buttons"` feature to use this component.
+use yew::{function_component, html, use_state, Callback, Children, ContextProvider, Properties};
+
+/// How the toggled state of the buttons is managed by the group
+#[derive(PartialEq, Clone, Copy, Debug, Default)]
+pub enum XButtonsTracking {
+    /// Buttons are managing their own state
+    #[default]
+    None,
+    /// At most one button is toggled. Clicking the toggled button turns it off.
+    SingleSelect,
+    /// One button is always toggled, like radio buttons
+    AtLeastOne,
+    /// Any number of buttons can be toggled
+    MultiSelect,
+}
+
+/// Properties for XButtons element
+#[derive(PartialEq, Properties)]
+pub struct XButtonsProps {
+    #[prop_or_default]
+    pub tracking: XButtonsTracking,
+    /// Values of the toggled buttons. When given, the owner controls the selection and should
+    /// update it in `onchange`. Otherwise the group keeps its own selection.
+    #[prop_or_default]
+    pub value: Option<Vec<String>>,
+    /// Initial selection when `value` is not given.
+    #[prop_or_default]
+    pub default_value: Vec<String>,
+    /// Stack the buttons vertically
+    #[prop_or_default]
+    pub vertical: bool,
+    /// Fires with the values of the toggled buttons when the user changes the selection.
+    #[prop_or_default]
+    pub onchange: Option<Callback<Vec<String>>>,
+    #[prop_or_default]
+    pub children: Children,
+}
+
+/// Selection shared with the buttons of a tracking group
+#[derive(PartialEq, Clone)]
+pub(crate) struct XButtonsContext {
+    pub(crate) tracking: XButtonsTracking,
+    pub(crate) selected: Vec<String>,
+    pub(crate) on_change: Callback<Vec<String>>,
+}
+
+impl XButtonsContext {
+    pub(crate) fn is_toggled(&self, value: &str) -> bool {
+        self.selected.iter().any(|v| v == value)
+    }
+
+    /// Selection after the button with the given value is clicked
+    pub(crate) fn toggle(&self, value: &str) -> Vec<String> {
+        let toggled = self.is_toggled(value);
+        match self.tracking {
+            XButtonsTracking::None => self.selected.clone(),
+            XButtonsTracking::SingleSelect if toggled => Vec::new(),
+            XButtonsTracking::SingleSelect | XButtonsTracking::AtLeastOne => {
+                vec![String::from(value)]
+            }
+            XButtonsTracking::MultiSelect if toggled => self
+                .selected
+                .iter()
+                .filter(|v| *v != value)
+                .cloned()
+                .collect(),
+            XButtonsTracking::MultiSelect => {
+                let mut selected = self.selected.clone();
+                selected.push(String::from(value));
+                selected
+            }
+        }
+    }
+}
+
+/// XButtons component
+#[function_component]
+pub fn XButtons(props: &XButtonsProps) -> yew::Html {
+    // Selection of the uncontrolled groups
+    let own_value = use_state(|| props.default_value.clone());
+    let selected = props.value.clone().unwrap_or_else(|| (*own_value).clone());
+
+    let on_change = {
+        let controlled = props.value.is_some();
+        let onchange = props.onchange.clone();
+        Callback::from(move |selected: Vec<String>| {
+            if !controlled {
+                own_value.set(selected.clone());
+            }
+            if let Some(onchange) = &onchange {
+                onchange.emit(selected);
+            }
+        })
+    };
+
+    let context = XButtonsContext {
+        tracking: props.tracking,
+        selected,
+        on_change,
+    };
+
+    let mut classes = String::from("x-buttons");
+    if props.vertical {
+        classes.push_str(" vertical");
+    }
+
+    html! {
+        <div role="group" class={classes}>
+            <ContextProvider<XButtonsContext> {context}>
+                {for props.children.iter()}
+            </ContextProvider<XButtonsContext>>
+        </div>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{XButtonsContext, XButtonsTracking};
+    use yew::Callback;
+
+    fn group(tracking: XButtonsTracking, selected: &[&str]) -> XButtonsContext {
+        XButtonsContext {
+            tracking,
+            selected: selected.iter().map(|v| String::from(*v)).collect(),
+            on_change: Callback::noop(),
+        }
+    }
+
+    #[test]
+    fn single_select_replaces_and_clears() {
+        let buttons = group(XButtonsTracking::SingleSelect, &["left"]);
+        assert_eq!(buttons.toggle("right"), vec!["right"]);
+        assert!(buttons.toggle("left").is_empty());
+        assert_eq!(group(XButtonsTracking::SingleSelect, &[]).toggle("left"), vec!["left"]);
+    }
+
+    #[test]
+    fn at_least_one_keeps_the_last_button() {
+        let buttons = group(XButtonsTracking::AtLeastOne, &["left"]);
+        assert_eq!(buttons.toggle("left"), vec!["left"]);
+        assert_eq!(buttons.toggle("center"), vec!["center"]);
+    }
+
+    #[test]
+    fn multi_select_adds_and_removes() {
+        let buttons = group(XButtonsTracking::MultiSelect, &["bold"]);
+        assert_eq!(buttons.toggle("italic"), vec!["bold", "italic"]);
+        assert!(buttons.toggle("bold").is_empty());
+
+        let buttons = group(XButtonsTracking::MultiSelect, &["bold", "italic", "underline"]);
+        assert_eq!(buttons.toggle("italic"), vec!["bold", "underline"]);
+    }
+
+    #[test]
+    fn none_keeps_the_selection() {
+        let buttons = group(XButtonsTracking::None, &["left"]);
+        assert_eq!(buttons.toggle("right"), vec!["left"]);
+    }
+}
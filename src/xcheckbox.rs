@@ -0,0 +1,163 @@
+//! `XCheckbox` is a box which can be toggled on and off, with an optional "mixed" state.
+//!
+//! Label inside the checkbox:-
+//! ```
+//! <XCheckbox default_toggled=true>
+//!     <XLabel>{"Enable notifications"}</XLabel>
+//! </XCheckbox>
+//! ```
+//!
+//! Label next to the checkbox:-
+//! ```
+//! <XCheckbox id="notifications" toggled={toggled} ontoggle={ontoggle} />
+//! <XLabel r#for="notifications">{"Enable notifications"}</XLabel>
+//! ```
+//!
+//! > Enable `"element-x-checkbox"` feature to use this component.
+use std::fmt::Write;
+
+use web_sys::{HtmlElement, KeyboardEvent, MouseEvent};
+use yew::{
+    function_component, html, use_context, use_node_ref, use_state, Callback, Children,
+    Properties,
+};
+
+use crate::calculate_computed_size;
+use crate::xcontainer::XContainerContext;
+use crate::XComponentSize;
+
+/// Properties for XCheckbox element
+#[derive(PartialEq, Properties)]
+pub struct XCheckboxProps {
+    /// Identifier of the checkbox, which a `XLabel` can refer with its `for` prop
+    #[prop_or_default]
+    pub id: Option<String>,
+    /// Whether this checkbox is toggled. When given, the owner controls the state and should
+    /// update it in `ontoggle`. Otherwise the checkbox keeps its own state.
+    #[prop_or_default]
+    pub toggled: Option<bool>,
+    /// Initial toggled state when `toggled` is not given.
+    #[prop_or_default]
+    pub default_toggled: bool,
+    /// Whether this checkbox is partially toggled (Eg:- some of its sub options are toggled).
+    /// Toggling a mixed checkbox turns it on, owner should clear this prop in `ontoggle`.
+    #[prop_or_default]
+    pub mixed: bool,
+    #[prop_or_default]
+    pub disabled: bool,
+    #[prop_or_default]
+    pub size: Option<XComponentSize>,
+    /// When the user toggles the checkbox. Receives the new toggled state.
+    #[prop_or_default]
+    pub ontoggle: Option<Callback<(MouseEvent, bool)>>,
+    /// Classes to apply for root element
+    #[prop_or_default]
+    pub class: Option<String>,
+    /// Styles to apply for root element
+    #[prop_or_default]
+    pub style: Option<String>,
+    /// Label of the checkbox
+    #[prop_or_default]
+    pub children: Children,
+}
+
+/// XCheckbox component
+#[function_component]
+pub fn XCheckbox(props: &XCheckboxProps) -> yew::Html {
+    let root_ref = use_node_ref();
+    let own_toggled = use_state(|| props.default_toggled);
+    let toggled = props.toggled.unwrap_or(*own_toggled);
+
+    let mut classes = String::from("x-checkbox");
+
+    if let Some(user_class) = props.class.clone() {
+        classes.push(' ');
+        classes.push_str(user_class.trim());
+    }
+
+    if toggled {
+        classes.push_str(" toggled");
+    }
+
+    if props.mixed {
+        classes.push_str(" mixed");
+    }
+
+    if props.disabled {
+        classes.push_str(" disabled");
+    }
+
+    if let Some(size) = props.size.clone() {
+        write!(classes, " size-{}", size.to_string()).unwrap();
+    }
+
+    let context =
+        use_context::<XContainerContext>().expect("XContainer should be the root element");
+
+    let computed_size = calculate_computed_size(props.size.clone(), context.size.clone());
+    write!(classes, " computedsize-{}", computed_size.to_string()).unwrap();
+
+    let onclick = {
+        let ontoggle = props.ontoggle.clone();
+        let disabled = props.disabled;
+        let mixed = props.mixed;
+        let controlled = props.toggled.is_some();
+        let own_toggled = own_toggled.clone();
+        Callback::from(move |e: MouseEvent| {
+            if disabled {
+                return;
+            }
+            let new_toggled = mixed || !toggled;
+            if !controlled {
+                own_toggled.set(new_toggled);
+            }
+            if let Some(ontoggle) = &ontoggle {
+                ontoggle.emit((e, new_toggled));
+            }
+        })
+    };
+
+    // Space toggles the checkbox like a click
+    let onkeydown = {
+        let root_ref = root_ref.clone();
+        let disabled = props.disabled;
+        Callback::from(move |e: KeyboardEvent| {
+            if e.key() == " " && !disabled {
+                // Prevent scrolling the page
+                e.prevent_default();
+                if !e.repeat() {
+                    if let Some(root) = root_ref.cast::<HtmlElement>() {
+                        root.click();
+                    }
+                }
+            }
+        })
+    };
+
+    let aria_checked = match (props.mixed, toggled) {
+        (true, _) => "mixed",
+        (false, true) => "true",
+        (false, false) => "false",
+    };
+
+    html! {
+        <div
+            ref={root_ref}
+            id={props.id.clone()}
+            role="checkbox"
+            tabindex={if props.disabled {"-1"} else {"0"}}
+            aria-checked={aria_checked}
+            aria-disabled={if props.disabled {"true"} else {"false"}}
+            style={props.style.clone()}
+            {onclick}
+            {onkeydown}
+            class={classes}>
+            <div class="x-checkbox-indicator">
+                <svg class="x-checkbox-checkmark" viewBox="0 0 100 100" preserveAspectRatio="none">
+                    <path></path>
+                </svg>
+            </div>
+            {for props.children.iter()}
+        </div>
+    }
+}
@@ -67,11 +67,16 @@ pub enum XContainerMessage {
 #[derive(Clone, PartialEq)]
 pub struct XContainerContext {
     pub size: ComputedSize,
+    /// Keyboard shortcuts which are working anywhere inside the container
+    #[cfg(feature = "element-x-shortcut")]
+    pub shortcuts: crate::xshortcut::XShortcutRegistry,
 }
 
 pub struct XContainer {
     #[cfg(feature = "feature-intl")]
     pub intl_state: crate::intl::Intl,
+    #[cfg(feature = "element-x-shortcut")]
+    shortcuts: crate::xshortcut::XShortcutRegistry,
 }
 
 impl Component for XContainer {
@@ -118,6 +123,8 @@ impl Component for XContainer {
 
                 intl
             },
+            #[cfg(feature = "element-x-shortcut")]
+            shortcuts: crate::xshortcut::XShortcutRegistry::default(),
         }
     }
 
@@ -156,9 +163,27 @@ impl Component for XContainer {
     }
 
     fn view(&self, ctx: &yew::Context<Self>) -> yew::Html {
+        let context = XContainerContext {
+            size: ctx.props().size.clone(),
+            #[cfg(feature = "element-x-shortcut")]
+            shortcuts: self.shortcuts.clone(),
+        };
+
+        #[cfg(feature = "element-x-shortcut")]
+        let onkeydown = {
+            let shortcuts = self.shortcuts.clone();
+            Some(yew::Callback::from(move |e: yew::events::KeyboardEvent| {
+                if !e.default_prevented() && shortcuts.dispatch(&e) {
+                    e.prevent_default();
+                }
+            }))
+        };
+        #[cfg(not(feature = "element-x-shortcut"))]
+        let onkeydown: Option<yew::Callback<yew::events::KeyboardEvent>> = None;
+
         let content: yew::Html = html! {
-            <div class="x-container">
-                <ContextProvider<XContainerContext> context={XContainerContext {size: ctx.props().size.clone()}}>
+            <div class="x-container" {onkeydown}>
+                <ContextProvider<XContainerContext> {context}>
                     {for ctx.props().children.iter()}
                 </ContextProvider<XContainerContext>>
             </div>
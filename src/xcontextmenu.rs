@@ -0,0 +1,305 @@
+//! `XContextMenu` wraps an area and opens its `XMenu` at the pointer location when the area is
+//! right-clicked or long-pressed on touch screens. The menu is closed when an item is
+//! triggered, or on Escape, an outside click, window blur or scrolling.
+//!
+//! ```
+//! let menu = html_nested! {
+//!     <XMenu>
+//!         <XMenuItem><XLabel>{"Copy"}</XLabel></XMenuItem>
+//!         <XMenuItem><XLabel>{"Paste"}</XLabel></XMenuItem>
+//!     </XMenu>
+//! };
+//!
+//! <XContextMenu {menu}>
+//!     <div class="canvas"/>
+//! </XContextMenu>
+//! ```
+//!
+//! > Enable `"element-x-contextmenu"` feature to use this component.
+use std::time::Duration;
+
+use prokio::time::sleep;
+use wasm_bindgen::JsCast;
+use web_sys::{window, Element, Event, HtmlElement, KeyboardEvent, MouseEvent, Node, PointerEvent};
+use yew::virtual_dom::VChild;
+use yew::{html, Callback, Children, Component, ContextProvider, NodeRef, Properties};
+
+use crate::{
+    utils::{min_pressed_time, EventListener},
+    xmenu::{XMenu, XMenuDismiss, XMenuOpen, XMenuOpenerContext},
+};
+
+/// Distance (in pixels) the finger can move before a long press is cancelled
+const LONG_PRESS_TOLERANCE: f64 = 10.0;
+
+/// Properties for XContextMenu element
+#[derive(PartialEq, Properties)]
+pub struct XContextMenuProps {
+    /// Whether the context menu is disabled or not. Native context menu is shown when disabled.
+    #[prop_or_default]
+    pub disabled: bool,
+    /// Menu to open. Create it with `html_nested!`.
+    #[prop_or_default]
+    pub menu: Option<VChild<XMenu>>,
+    /// Contents of the area
+    #[prop_or_default]
+    pub children: Children,
+}
+
+#[doc(hidden)]
+pub enum XContextMenuMessage {
+    ContextMenu(MouseEvent),
+    PointerDown(PointerEvent),
+    PointerMove(PointerEvent),
+    PointerUp(PointerEvent),
+    LongPress(u32),
+    Dismissed(XMenuDismiss),
+    WindowPointerDown(Event),
+    WindowKeyDown(KeyboardEvent),
+    WindowScroll(Event),
+    WindowBlur,
+}
+
+/// XContextMenu component
+pub struct XContextMenu {
+    node_ref: NodeRef,
+    open: Option<XMenuOpen>,
+    /// Menu was opened with the keyboard, so the first item is focused
+    focus_first: bool,
+    /// Element to focus again after the menu is closed with the keyboard
+    previous_focus: Option<HtmlElement>,
+    /// Pointer id and the position of the touch which may become a long press
+    touch: Option<(i32, f64, f64)>,
+    /// Used to ignore timers of cancelled long presses
+    long_press_id: u32,
+    /// Menu was opened by the current long press, ignore the following `contextmenu` event
+    long_pressed: bool,
+    on_dismiss: Callback<XMenuDismiss>,
+    /// Listeners installed while the menu is opened
+    _window_listeners: Vec<EventListener>,
+}
+
+impl XContextMenu {
+    /// Root element of the `XMenu` inside this component
+    fn menu_element(&self) -> Option<Element> {
+        self.node_ref
+            .cast::<Element>()?
+            .query_selector(":scope > .x-menu")
+            .ok()
+            .flatten()
+    }
+
+    fn in_menu(&self, target: Option<web_sys::EventTarget>) -> bool {
+        match (self.menu_element(), target.and_then(|t| t.dyn_into::<Node>().ok())) {
+            (Some(menu), Some(target)) => menu.contains(Some(&target)),
+            _ => false,
+        }
+    }
+
+    fn open(&mut self, ctx: &yew::Context<Self>, x: f64, y: f64, focus_first: bool) {
+        let window = window().unwrap();
+        self.previous_focus = window
+            .document()
+            .unwrap()
+            .active_element()
+            .and_then(|e| e.dyn_into::<HtmlElement>().ok());
+        self.open = Some(XMenuOpen::AtPoint(x, y));
+        self.focus_first = focus_first;
+
+        if self._window_listeners.is_empty() {
+            let link = ctx.link().clone();
+            let pointerdown = EventListener::with_capture(&window, "pointerdown", true, move |e| {
+                link.send_message(XContextMenuMessage::WindowPointerDown(e))
+            });
+            let link = ctx.link().clone();
+            let keydown = EventListener::new(&window, "keydown", move |e| {
+                link.send_message(XContextMenuMessage::WindowKeyDown(e.unchecked_into()))
+            });
+            let link = ctx.link().clone();
+            let scroll = EventListener::with_capture(&window, "scroll", true, move |e| {
+                link.send_message(XContextMenuMessage::WindowScroll(e))
+            });
+            let link = ctx.link().clone();
+            let blur = EventListener::new(&window, "blur", move |_| {
+                link.send_message(XContextMenuMessage::WindowBlur)
+            });
+            self._window_listeners = vec![pointerdown, keydown, scroll, blur];
+        }
+    }
+
+    fn close(&mut self, restore_focus: bool) -> bool {
+        self._window_listeners.clear();
+        let previous_focus = self.previous_focus.take();
+        if self.open.take().is_none() {
+            return false;
+        }
+        if restore_focus {
+            if let Some(previous_focus) = previous_focus {
+                previous_focus.focus().ok();
+            }
+        }
+        true
+    }
+
+    fn cancel_long_press(&mut self) {
+        self.touch = None;
+        self.long_press_id += 1;
+    }
+}
+
+impl Component for XContextMenu {
+    type Message = XContextMenuMessage;
+    type Properties = XContextMenuProps;
+
+    fn create(ctx: &yew::Context<Self>) -> Self {
+        XContextMenu {
+            node_ref: NodeRef::default(),
+            open: None,
+            focus_first: false,
+            previous_focus: None,
+            touch: None,
+            long_press_id: 0,
+            long_pressed: false,
+            on_dismiss: ctx.link().callback(XContextMenuMessage::Dismissed),
+            _window_listeners: Vec::new(),
+        }
+    }
+
+    fn changed(&mut self, ctx: &yew::Context<Self>, _old_props: &Self::Properties) -> bool {
+        if ctx.props().disabled {
+            self.cancel_long_press();
+            self.close(false);
+        }
+        true
+    }
+
+    fn update(&mut self, ctx: &yew::Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            XContextMenuMessage::ContextMenu(e) => {
+                if ctx.props().disabled {
+                    return false;
+                }
+                e.prevent_default();
+                if self.long_pressed || self.in_menu(e.target()) {
+                    return false;
+                }
+
+                // Context menu key gives zero coordinates, open at the corner of the area instead
+                let keyboard = e.client_x() == 0 && e.client_y() == 0 && e.detail() == 0;
+                let (x, y) = if keyboard {
+                    let rect = self
+                        .node_ref
+                        .cast::<Element>()
+                        .unwrap()
+                        .get_bounding_client_rect();
+                    (rect.left(), rect.top())
+                } else {
+                    (e.client_x() as f64, e.client_y() as f64)
+                };
+                self.open(ctx, x, y, keyboard);
+                true
+            }
+            XContextMenuMessage::PointerDown(e) => {
+                self.long_pressed = false;
+                if ctx.props().disabled
+                    || e.pointer_type() != "touch"
+                    || !e.is_primary()
+                    || self.in_menu(e.target())
+                {
+                    return false;
+                }
+
+                self.cancel_long_press();
+                self.touch = Some((e.pointer_id(), e.client_x() as f64, e.client_y() as f64));
+                let id = self.long_press_id;
+                ctx.link().send_future(async move {
+                    sleep(Duration::from_millis(min_pressed_time("touch"))).await;
+                    XContextMenuMessage::LongPress(id)
+                });
+                false
+            }
+            XContextMenuMessage::PointerMove(e) => {
+                if let Some((pointer_id, x, y)) = self.touch {
+                    let distance =
+                        (e.client_x() as f64 - x).hypot(e.client_y() as f64 - y);
+                    if e.pointer_id() == pointer_id && distance > LONG_PRESS_TOLERANCE {
+                        self.cancel_long_press();
+                    }
+                }
+                false
+            }
+            XContextMenuMessage::PointerUp(e) => {
+                if matches!(self.touch, Some((pointer_id, _, _)) if pointer_id == e.pointer_id()) {
+                    self.cancel_long_press();
+                }
+                false
+            }
+            XContextMenuMessage::LongPress(id) => {
+                if id != self.long_press_id {
+                    return false;
+                }
+                match self.touch.take() {
+                    Some((_, x, y)) => {
+                        self.long_pressed = true;
+                        self.open(ctx, x, y, false);
+                        true
+                    }
+                    None => false,
+                }
+            }
+            XContextMenuMessage::Dismissed(reason) => self.close(reason == XMenuDismiss::Escape),
+            XContextMenuMessage::WindowPointerDown(e) => {
+                if self.in_menu(e.target()) {
+                    return false;
+                }
+                self.close(false)
+            }
+            XContextMenuMessage::WindowKeyDown(e) => {
+                if e.key() != "Escape" {
+                    return false;
+                }
+                self.close(true)
+            }
+            XContextMenuMessage::WindowScroll(e) => {
+                // Scrolling the menu itself should not close it
+                if self.in_menu(e.target()) {
+                    return false;
+                }
+                self.close(false)
+            }
+            XContextMenuMessage::WindowBlur => self.close(false),
+        }
+    }
+
+    fn view(&self, ctx: &yew::Context<Self>) -> yew::Html {
+        let props = ctx.props();
+
+        let opener = XMenuOpenerContext {
+            open: self.open.clone(),
+            focus_first: self.focus_first,
+            submenu: false,
+            on_dismiss: self.on_dismiss.clone(),
+        };
+
+        let mut classes = String::from("x-contextmenu");
+        if self.open.is_some() {
+            classes.push_str(" opened");
+        }
+
+        html! {
+            <div
+                ref={self.node_ref.clone()}
+                oncontextmenu={ctx.link().callback(XContextMenuMessage::ContextMenu)}
+                onpointerdown={ctx.link().callback(XContextMenuMessage::PointerDown)}
+                onpointermove={ctx.link().callback(XContextMenuMessage::PointerMove)}
+                onpointerup={ctx.link().callback(XContextMenuMessage::PointerUp)}
+                onpointercancel={ctx.link().callback(XContextMenuMessage::PointerUp)}
+                class={classes}>
+                {for props.children.iter()}
+                <ContextProvider<XMenuOpenerContext> context={opener}>
+                    {props.menu.clone()}
+                </ContextProvider<XMenuOpenerContext>>
+            </div>
+        }
+    }
+}
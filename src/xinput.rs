@@ -0,0 +1,506 @@
+//! `XInput` is a single line text field, which is validated with the standard constraints
+//! (`required`, `minlength`, `maxlength`, `pattern` and the type) and an optional custom
+//! validator. Validation errors are displayed in an error tooltip after the field is left once.
+//!
+//! ```
+//! <XInput r#type={XInputType::Email} required=true oninput={on_email_input}>
+//!     <XLabel>{"Email"}</XLabel>
+//! </XInput>
+//! ```
+//!
+//! Custom validation:-
+//! ```
+//! let validator = Callback::from(|value: String| {
+//!     (value.len() < 3).then(|| String::from("Username is too short"))
+//! });
+//! html! {
+//!     <XInput {validator} prefix={html! {<span>{"@"}</span>}}>
+//!         <XLabel>{"Username"}</XLabel>
+//!     </XInput>
+//! }
+//! ```
+//!
+//! `XLabel` children are displayed as the placeholder while the field is empty.
+//!
+//! Length errors are formatted with `length_message`, or with the `x-input-too-short` and
+//! `x-input-too-long` messages of the `Intl` context (Arguments are `$limit` and `$length`).
+//! Otherwise the length is displayed against the limit (Eg:- `"2 / 8"`).
+//!
+//! > Enable `"element-x-input"` feature to use this component.
+use std::fmt::{self, Write};
+
+use wasm_bindgen::JsCast;
+use web_sys::{
+    Event, FocusEvent, HtmlInputElement, InputEvent, KeyboardEvent, MouseEvent, Node, PointerEvent,
+};
+use yew::{
+    function_component, html, use_context, use_effect, use_node_ref, use_state, Callback,
+    Children, Html, Properties,
+};
+
+use crate::calculate_computed_size;
+use crate::xcontainer::XContainerContext;
+use crate::xtooltip::{XTooltip, XTooltipType};
+use crate::XComponentSize;
+
+/// Type of the XInput component
+#[derive(PartialEq, Clone, Copy, Debug, Default)]
+pub enum XInputType {
+    #[default]
+    Text,
+    Email,
+    Url,
+    Password,
+    /// Has a button to clear the value
+    Search,
+}
+
+impl fmt::Display for XInputType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            XInputType::Text => "text",
+            XInputType::Email => "email",
+            XInputType::Url => "url",
+            XInputType::Password => "password",
+            XInputType::Search => "search",
+        })
+    }
+}
+
+/// A value which is not between `minlength` and `maxlength`. Lengths are counted in UTF-16 code
+/// units like the browsers.
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum XInputLengthError {
+    TooShort { minlength: u32, length: u32 },
+    TooLong { maxlength: u32, length: u32 },
+}
+
+impl XInputLengthError {
+    /// Minimum or maximum length which the value breaks
+    pub fn limit(&self) -> u32 {
+        match self {
+            Self::TooShort { minlength, .. } => *minlength,
+            Self::TooLong { maxlength, .. } => *maxlength,
+        }
+    }
+
+    /// Current length of the value
+    pub fn length(&self) -> u32 {
+        match self {
+            Self::TooShort { length, .. } | Self::TooLong { length, .. } => *length,
+        }
+    }
+
+    #[cfg(feature = "feature-intl")]
+    fn intl_message(&self, intl: &crate::intl::Intl) -> Option<String> {
+        let id = match self {
+            Self::TooShort { .. } => "x-input-too-short",
+            Self::TooLong { .. } => "x-input-too-long",
+        };
+        let mut args = fluent::FluentArgs::new();
+        args.set("limit", self.limit());
+        args.set("length", self.length());
+        intl.get(id, Some(&args)).ok().flatten()
+    }
+}
+
+/// Properties for XInput element
+#[derive(PartialEq, Properties)]
+pub struct XInputProps {
+    /// Value of the field. When given, the owner controls the value and should update it in
+    /// `oninput`. Otherwise the field keeps its own value.
+    #[prop_or_default]
+    pub value: Option<String>,
+    /// Initial value when `value` is not given.
+    #[prop_or_default]
+    pub default_value: String,
+    #[prop_or_default]
+    pub r#type: XInputType,
+    /// Name of the field in the form data
+    #[prop_or_default]
+    pub name: Option<String>,
+    #[prop_or_default]
+    pub required: bool,
+    #[prop_or_default]
+    pub minlength: Option<u32>,
+    #[prop_or_default]
+    pub maxlength: Option<u32>,
+    /// Formats the error message of a value which is shorter than `minlength` or longer than
+    /// `maxlength`
+    #[prop_or_default]
+    pub length_message: Option<Callback<XInputLengthError, String>>,
+    /// Regular expression which the whole value should match
+    #[prop_or_default]
+    pub pattern: Option<String>,
+    /// Validates the value after the standard constraints. Returns the error message when the
+    /// value is invalid.
+    #[prop_or_default]
+    pub validator: Option<Callback<String, Option<String>>>,
+    /// Error given by the owner (Eg:- rejected by the server). Displayed even when the field is
+    /// not left yet.
+    #[prop_or_default]
+    pub error: Option<String>,
+    #[prop_or_default]
+    pub disabled: bool,
+    #[prop_or_default]
+    pub size: Option<XComponentSize>,
+    /// Displayed before the text (Eg:- an icon)
+    #[prop_or_default]
+    pub prefix: Option<Html>,
+    /// Displayed after the text (Eg:- a unit)
+    #[prop_or_default]
+    pub suffix: Option<Html>,
+    /// When the user changes the value
+    #[prop_or_default]
+    pub oninput: Option<Callback<String>>,
+    /// When the user commits the value (Eg:- by leaving the field)
+    #[prop_or_default]
+    pub onchange: Option<Callback<String>>,
+    /// Classes to apply for root element
+    #[prop_or_default]
+    pub class: Option<String>,
+    /// Styles to apply for root element
+    #[prop_or_default]
+    pub style: Option<String>,
+    /// Placeholder label
+    #[prop_or_default]
+    pub children: Children,
+}
+
+/// XInput component
+#[function_component]
+pub fn XInput(props: &XInputProps) -> yew::Html {
+    let input_ref = use_node_ref();
+    let own_value = use_state(|| props.default_value.clone());
+    let value = props.value.clone().unwrap_or_else(|| (*own_value).clone());
+    // Error message of the current value
+    let validation = use_state(|| None::<String>);
+    // Errors are only displayed after leaving the field once
+    let touched = use_state(|| false);
+    let focused = use_state(|| false);
+    let hovered = use_state(|| false);
+
+    #[cfg(feature = "feature-intl")]
+    let intl = use_context::<crate::intl::Intl>();
+
+    // Constraints and the value may change with any render
+    {
+        let input_ref = input_ref.clone();
+        let validator = props.validator.clone();
+        let validation = validation.clone();
+        let (minlength, maxlength) = (props.minlength, props.maxlength);
+        let length_message = props.length_message.clone();
+        #[cfg(feature = "feature-intl")]
+        let intl = intl.clone();
+        let format_length = move |error: XInputLengthError| {
+            if let Some(length_message) = &length_message {
+                return length_message.emit(error);
+            }
+            #[cfg(feature = "feature-intl")]
+            if let Some(message) = intl.as_ref().and_then(|intl| error.intl_message(intl)) {
+                return message;
+            }
+            format!("{} / {}", error.length(), error.limit())
+        };
+        use_effect(move || {
+            if let Some(input) = input_ref.cast::<HtmlInputElement>() {
+                let length_error = length_error(&input.value(), minlength, maxlength);
+                let message = validate(&input, length_error.map(format_length), validator.as_ref());
+                if *validation != message {
+                    validation.set(message);
+                }
+            }
+        });
+    }
+
+    let error = props.error.clone().or_else(|| {
+        if *touched {
+            (*validation).clone()
+        } else {
+            None
+        }
+    });
+
+    let mut classes = String::from("x-input");
+
+    if let Some(user_class) = props.class.clone() {
+        classes.push(' ');
+        classes.push_str(user_class.trim());
+    }
+
+    if value.is_empty() {
+        classes.push_str(" empty");
+    }
+
+    if error.is_some() {
+        classes.push_str(" error");
+    }
+
+    if props.disabled {
+        classes.push_str(" disabled");
+    }
+
+    if let Some(size) = props.size.clone() {
+        write!(classes, " size-{}", size.to_string()).unwrap();
+    }
+
+    let context =
+        use_context::<XContainerContext>().expect("XContainer should be the root element");
+
+    let computed_size = calculate_computed_size(props.size.clone(), context.size.clone());
+    write!(classes, " computedsize-{}", computed_size.to_string()).unwrap();
+
+    let set_value = {
+        let controlled = props.value.is_some();
+        let oninput = props.oninput.clone();
+        move |value: String| {
+            if !controlled {
+                own_value.set(value.clone());
+            }
+            if let Some(oninput) = &oninput {
+                oninput.emit(value);
+            }
+        }
+    };
+
+    let oninput = {
+        let input_ref = input_ref.clone();
+        let set_value = set_value.clone();
+        Callback::from(move |_e: InputEvent| {
+            if let Some(input) = input_ref.cast::<HtmlInputElement>() {
+                set_value(input.value());
+            }
+        })
+    };
+
+    let onchange = {
+        let input_ref = input_ref.clone();
+        let onchange = props.onchange.clone();
+        Callback::from(move |_e: Event| {
+            if let (Some(input), Some(onchange)) = (input_ref.cast::<HtmlInputElement>(), &onchange)
+            {
+                onchange.emit(input.value());
+            }
+        })
+    };
+
+    let onfocusin = {
+        let focused = focused.clone();
+        Callback::from(move |_e: FocusEvent| focused.set(true))
+    };
+
+    let onfocusout = {
+        let focused = focused.clone();
+        let touched = touched.clone();
+        Callback::from(move |e: FocusEvent| {
+            // Focus moved to the clear button or the suffix
+            let root = e.current_target().and_then(|t| t.dyn_into::<Node>().ok());
+            let related = e.related_target().and_then(|t| t.dyn_into::<Node>().ok());
+            if let (Some(root), Some(related)) = (root, related) {
+                if root.contains(Some(&related)) {
+                    return;
+                }
+            }
+            focused.set(false);
+            touched.set(true);
+        })
+    };
+
+    let onpointerenter = {
+        let hovered = hovered.clone();
+        Callback::from(move |_e: PointerEvent| hovered.set(true))
+    };
+
+    let onpointerleave = {
+        let hovered = hovered.clone();
+        Callback::from(move |_e: PointerEvent| hovered.set(false))
+    };
+
+    // Clicking the padding or the placeholder focuses the text
+    let onclick = {
+        let input_ref = input_ref.clone();
+        Callback::from(move |_e: MouseEvent| {
+            if let Some(input) = input_ref.cast::<HtmlInputElement>() {
+                input.focus().ok();
+            }
+        })
+    };
+
+    let clearable = props.r#type == XInputType::Search && !value.is_empty() && !props.disabled;
+
+    // Clears the value and moves the focus back to the text
+    let clear = {
+        let input_ref = input_ref.clone();
+        let set_value = set_value.clone();
+        let onchange = props.onchange.clone();
+        move || {
+            set_value(String::new());
+            if let Some(onchange) = &onchange {
+                onchange.emit(String::new());
+            }
+            if let Some(input) = input_ref.cast::<HtmlInputElement>() {
+                input.focus().ok();
+            }
+        }
+    };
+
+    // Escape clears the search text
+    let onkeydown = {
+        let clear = clear.clone();
+        Callback::from(move |e: KeyboardEvent| {
+            if e.key() == "Escape" && clearable {
+                e.prevent_default();
+                clear();
+            }
+        })
+    };
+
+    let clear_button = clearable.then(|| {
+        // Keep the focus in the text
+        let onpointerdown = Callback::from(|e: PointerEvent| e.prevent_default());
+        let onclick = {
+            let clear = clear.clone();
+            Callback::from(move |_e: MouseEvent| clear())
+        };
+        let onkeydown = Callback::from(move |e: KeyboardEvent| {
+            if e.key() == "Enter" || e.key() == " " {
+                e.prevent_default();
+                clear();
+            }
+        });
+        html! {
+            <svg
+                class="x-input-clear-button"
+                role="button"
+                tabindex="0"
+                aria-label="Clear"
+                viewBox="0 0 100 100"
+                preserveAspectRatio="none"
+                {onpointerdown}
+                {onclick}
+                {onkeydown}>
+                <path class="x-input-clear-button-path"></path>
+            </svg>
+        }
+    });
+
+    let tooltip = error.clone().map(|error| {
+        let open = *focused || *hovered;
+        html! {
+            <XTooltip anchor={input_ref.clone()} r#type={XTooltipType::Error} open={Some(open)}>
+                {error}
+            </XTooltip>
+        }
+    });
+
+    html! {
+        <div
+            style={props.style.clone()}
+            {onfocusin}
+            {onfocusout}
+            {onpointerenter}
+            {onpointerleave}
+            {onclick}
+            class={classes}>
+            {for props.prefix.clone()}
+            {for props.children.iter()}
+            <input
+                ref={input_ref}
+                class="x-input-input"
+                type={props.r#type.to_string()}
+                name={props.name.clone()}
+                value={value}
+                required={props.required}
+                minlength={props.minlength.map(|length| length.to_string())}
+                maxlength={props.maxlength.map(|length| length.to_string())}
+                pattern={props.pattern.clone()}
+                disabled={props.disabled}
+                aria-invalid={if error.is_some() {"true"} else {"false"}}
+                {oninput}
+                {onchange}
+                {onkeydown} />
+            {for clear_button}
+            {for props.suffix.clone()}
+            {for tooltip}
+        </div>
+    }
+}
+
+/// Checks the standard constraints, the length and the custom validator. Custom errors are also
+/// set as the custom validity, so the field is matching `:invalid`.
+fn validate(
+    input: &HtmlInputElement,
+    length_error: Option<String>,
+    validator: Option<&Callback<String, Option<String>>>,
+) -> Option<String> {
+    let custom =
+        length_error.or_else(|| validator.and_then(|validator| validator.emit(input.value())));
+    input.set_custom_validity(custom.as_deref().unwrap_or(""));
+    if input.check_validity() {
+        None
+    } else {
+        custom.or_else(|| Some(input.validation_message().unwrap_or_default()))
+    }
+}
+
+/// Browsers are only checking the length after the user edits the value, so it is checked
+/// explicitly. An empty value is left to `required`.
+fn length_error(
+    value: &str,
+    minlength: Option<u32>,
+    maxlength: Option<u32>,
+) -> Option<XInputLengthError> {
+    let length = value.encode_utf16().count() as u32;
+    match (minlength, maxlength) {
+        (Some(minlength), _) if length > 0 && length < minlength => {
+            Some(XInputLengthError::TooShort { minlength, length })
+        }
+        (_, Some(maxlength)) if length > maxlength => {
+            Some(XInputLengthError::TooLong { maxlength, length })
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{length_error, XInputLengthError};
+
+    #[test]
+    fn too_short_value() {
+        assert_eq!(
+            length_error("ab", Some(3), None),
+            Some(XInputLengthError::TooShort {
+                minlength: 3,
+                length: 2
+            })
+        );
+        assert_eq!(length_error("abc", Some(3), None), None);
+    }
+
+    #[test]
+    fn empty_value_is_not_too_short() {
+        assert_eq!(length_error("", Some(3), None), None);
+    }
+
+    #[test]
+    fn too_long_value() {
+        let error = length_error("abcd", None, Some(3)).unwrap();
+        assert_eq!(
+            error,
+            XInputLengthError::TooLong {
+                maxlength: 3,
+                length: 4
+            }
+        );
+        assert_eq!((error.limit(), error.length()), (3, 4));
+        assert_eq!(length_error("abc", Some(1), Some(3)), None);
+    }
+
+    #[test]
+    fn length_is_counted_in_utf16_units() {
+        // Emoji are two UTF-16 code units
+        assert_eq!(length_error("😀", Some(2), None), None);
+        assert!(length_error("é", Some(2), None).is_some());
+    }
+}
@@ -6,12 +6,31 @@
 //!     <XLabel>{"My Button"}</XLabel>
 //! </XButton>
 //! ```
+//!
+//! A label can also name an element outside of it with `for`. Clicking the label clicks that
+//! element, and the element is described by the label with `aria-labelledby`.
+//!
+//! ```
+//! <XCheckbox id="notifications" />
+//! <XLabel r#for="notifications">{"Enable notifications"}</XLabel>
+//! ```
 
+use wasm_bindgen::JsCast;
+use web_sys::{window, Element, HtmlElement, MouseEvent};
 use yew::{Children, Component, html, Properties};
 
+use crate::utils::next_id;
+
 /// Properties for XLabel element
 #[derive(Properties, PartialEq)]
 pub struct XLabelProps {
+    /// Identifier of the element labelled by this label
+    #[prop_or_default]
+    pub r#for: Option<String>,
+    /// Identifier of the label, which the labelled element refers in `aria-labelledby`.
+    /// Generated when not given.
+    #[prop_or_default]
+    pub id: Option<String>,
     /// Whether that label is disabled or not
     #[prop_or_default]
     pub disabled: bool,
@@ -29,16 +48,107 @@ pub struct XLabelProps {
     pub style: Option<String>,
 }
 
+#[doc(hidden)]
+pub enum XLabelMessage {
+    Click(MouseEvent),
+}
+
 /// XLabel component
-pub struct XLabel;
+pub struct XLabel {
+    /// Identifier used when the `id` prop is not given
+    generated_id: String,
+    /// Labelled element and the identifier which it refers
+    linked: Option<(Element, String)>,
+}
+
+impl XLabel {
+    fn id(&self, ctx: &yew::Context<Self>) -> String {
+        ctx.props()
+            .id
+            .clone()
+            .unwrap_or_else(|| self.generated_id.clone())
+    }
+
+    fn target(ctx: &yew::Context<Self>) -> Option<Element> {
+        let target_id = ctx.props().r#for.as_ref()?;
+        window()?.document()?.get_element_by_id(target_id)
+    }
+
+    /// Refers the label from `aria-labelledby` of the labelled element
+    fn link(&mut self, ctx: &yew::Context<Self>) {
+        let (target, id) = match Self::target(ctx) {
+            Some(target) => (target, self.id(ctx)),
+            None => return,
+        };
+        let labelled_by = target.get_attribute("aria-labelledby").unwrap_or_default();
+        if !labelled_by.split_whitespace().any(|token| token == id) {
+            let labelled_by = format!("{} {}", labelled_by, id);
+            target.set_attribute("aria-labelledby", labelled_by.trim()).unwrap();
+        }
+        self.linked = Some((target, id));
+    }
+
+    /// Removes the reference added by `link`
+    fn unlink(&mut self) {
+        let (target, id) = match self.linked.take() {
+            Some(linked) => linked,
+            None => return,
+        };
+        if let Some(labelled_by) = target.get_attribute("aria-labelledby") {
+            let labelled_by: Vec<&str> = labelled_by
+                .split_whitespace()
+                .filter(|token| *token != id)
+                .collect();
+            if labelled_by.is_empty() {
+                target.remove_attribute("aria-labelledby").unwrap();
+            } else {
+                target.set_attribute("aria-labelledby", &labelled_by.join(" ")).unwrap();
+            }
+        }
+    }
+}
 
 impl Component for XLabel {
-    type Message = ();
+    type Message = XLabelMessage;
 
     type Properties = XLabelProps;
 
     fn create(_ctx: &yew::Context<Self>) -> Self {
-        XLabel
+        XLabel {
+            generated_id: next_id("x-label"),
+            linked: None,
+        }
+    }
+
+    fn update(&mut self, ctx: &yew::Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            XLabelMessage::Click(e) => {
+                // Links inside the label are working as usual
+                let on_link = e
+                    .target()
+                    .and_then(|target| target.dyn_into::<Element>().ok())
+                    .and_then(|target| target.closest("a").ok().flatten())
+                    .is_some();
+                if !ctx.props().disabled && !on_link {
+                    if let Some(target) = Self::target(ctx)
+                        .and_then(|target| target.dyn_into::<HtmlElement>().ok())
+                    {
+                        target.click();
+                    }
+                }
+                false
+            }
+        }
+    }
+
+    fn rendered(&mut self, ctx: &yew::Context<Self>, _first_render: bool) {
+        // Labelled element may be replaced or rendered later, so it is linked again
+        self.unlink();
+        self.link(ctx);
+    }
+
+    fn destroy(&mut self, _ctx: &yew::Context<Self>) {
+        self.unlink();
     }
 
     fn view(&self, ctx: &yew::Context<Self>) -> yew::Html {
@@ -58,7 +168,11 @@ impl Component for XLabel {
         }
         
         html! {
-            <div style={prop.style.clone().unwrap_or(String::from(""))} class={classes}>
+            <div
+                id={prop.id.clone().or_else(|| prop.r#for.as_ref().map(|_| self.id(ctx)))}
+                style={prop.style.clone().unwrap_or(String::from(""))}
+                onclick={ctx.link().callback(XLabelMessage::Click)}
+                class={classes}>
                 <div class="x-label-contents">
                     {for ctx.props().children.iter()}
                 </div>
@@ -1,60 +1,1002 @@
-use yew::{Component, Properties, html, Callback, NodeRef};
+//! `XMenu` is a popup menu holding `XMenuItem` elements. The menu is hidden until the `open`
+//! prop is given, which also describes where the menu should be placed.
+//!
+//! ```
+//! <XMenu open={Some(XMenuOpen::AtPoint(120.0, 80.0))} on_close_finished={on_closed}>
+//!     <XMenuItem><XLabel>{"Copy"}</XLabel></XMenuItem>
+//!     <hr/>
+//!     <XMenuItem><XLabel>{"Paste"}</XLabel></XMenuItem>
+//! </XMenu>
+//! ```
+//!
+//! > Enable `"element-x-menu"` feature to use this component.
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::time::Duration;
 
-use crate::{Transition, CSSEasing};
+use js_sys::Object;
+use prokio::time::sleep;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{window, Element, FocusEvent, HtmlElement, KeyboardEvent, PointerEvent};
+use yew::html::ChildrenRenderer;
+use yew::{html, Callback, Component, ContextHandle, ContextProvider, NodeRef, Properties};
 
-pub struct XMenu;
+use crate::{
+    positioning::{compute_position, contain, Alignment, PositionOptions, Rect, Side},
+    utils::{client_rect, new_animation, property_keyframes, window_rect},
+    CSSEasing, Transition,
+};
 
-#[derive(PartialEq)]
+/// Time to hover over an item before its submenu is expanded (in milliseconds)
+const SUBMENU_OPEN_DELAY: u64 = 150;
+/// Time to keep the expanded submenu while the pointer is heading towards it
+const SAFE_TRIANGLE_DELAY: u64 = 300;
+/// Typed characters are joined into one search text when typed within this time
+const TYPE_AHEAD_TIMEOUT: f64 = 500.0;
+
+/// Horizontal side to which the menu is aligned when it is opened next to an element or at a
+/// point.
+#[derive(PartialEq, Clone)]
 pub enum XMenuAlign {
     Start,
-    End
+    End,
 }
 
-#[derive(PartialEq)]
+/// Describes how the menu should be positioned when it is opening. New placements may be added
+/// later, so matches on this enum need a wildcard arm.
+#[derive(PartialEq, Clone)]
+#[non_exhaustive]
 pub enum XMenuOpen {
+    /// Place the menu so that the second element (usually an item inside the menu) is drawn
+    /// exactly over the first element.
     OverElement(NodeRef, NodeRef),
+    /// Place the menu so that the label of the toggled (or first) item is drawn exactly over
+    /// the given label.
     OverLabel(NodeRef),
+    /// Place the menu on the side of the given element. Used by submenus.
     NextToElement(NodeRef),
-    AtPoint(f64, f64)
+    /// Place the menu below the given element, or above it when there is no space below. Used
+    /// by menubars and buttons.
+    BelowElement(NodeRef),
+    /// Place the menu at the given client coordinates. Used by context menus.
+    AtPoint(f64, f64),
 }
 
 #[derive(PartialEq, Properties)]
 pub struct XMenuProps {
+    /// Animation to use when opening the menu. Supported properties are `"transform"` and
+    /// `"opacity"`.
     #[prop_or(Transition::new("transform", 100.0, CSSEasing::CubicBezier(0.4, 0.0, 0.2, 1.0)))]
     pub open_transition: Transition,
+    /// Animation to use when closing the menu. Supported properties are `"transform"` and
+    /// `"opacity"`.
     #[prop_or(Transition::new("opacity", 200.0, CSSEasing::CubicBezier(0.4, 0.0, 0.2, 1.0)))]
     pub close_transition: Transition,
     #[prop_or(XMenuAlign::Start)]
     pub align: XMenuAlign,
+    /// Opens the menu with the given placement. Menu will be closed when this is `None`.
     #[prop_or_default]
     pub open: Option<XMenuOpen>,
+    /// When the menu starts to open
     #[prop_or_default]
     pub on_open: Option<Callback<XMenuOpen>>,
+    /// When the open transition is finished
     #[prop_or_default]
     pub on_open_finished: Option<Callback<XMenuOpen>>,
+    /// When the menu starts to close
     #[prop_or_default]
     pub on_close: Option<Callback<()>>,
+    /// When the close transition is finished and the menu is hidden
+    #[prop_or_default]
+    pub on_close_finished: Option<Callback<()>>,
+    /// When the menu asks to be closed (Eg:- an item was triggered or Escape was pressed).
+    /// Owner of the `open` prop should set it to `None` in reply.
+    #[prop_or_default]
+    pub on_dismiss: Option<Callback<XMenuDismiss>>,
     #[prop_or_default]
-    pub on_close_finished: Option<Callback<()>>
+    pub children: ChildrenRenderer<XMenuChild>,
 }
 
+#[derive(PartialEq, Clone, derive_more::From)]
 pub enum XMenuChild {
-    #[cfg(feature="element-x-menuitem")]
-    Item(crate::xmenuitem::XMenuItem)
+    #[cfg(feature = "element-x-menuitem")]
+    Item(yew::virtual_dom::VChild<crate::xmenuitem::XMenuItem>),
+    Other(yew::Html),
+}
+
+impl Into<yew::Html> for XMenuChild {
+    fn into(self) -> yew::Html {
+        match self {
+            #[cfg(feature = "element-x-menuitem")]
+            Self::Item(child) => child.into(),
+            Self::Other(child) => child.into(),
+        }
+    }
+}
+
+/// Reason of a close request made by the menu itself.
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum XMenuDismiss {
+    /// An item was triggered. Whole chain of opened menus should be closed.
+    Triggered,
+    /// Escape key was pressed inside the menu.
+    Escape,
+    /// ArrowLeft key was pressed inside a submenu.
+    Collapse,
+}
+
+/// Provided by components which are owning a menu (Eg:- a `XMenuItem` with a submenu). The
+/// menu will be opened when the owner gives an `open` value, even if the `open` prop is `None`.
+#[doc(hidden)]
+#[derive(Clone, PartialEq)]
+pub struct XMenuOpenerContext {
+    pub(crate) open: Option<XMenuOpen>,
+    /// Focus the first item after opening (when opened with the keyboard)
+    pub(crate) focus_first: bool,
+    /// Menu is a submenu of another menu, so ArrowLeft key collapses it
+    pub(crate) submenu: bool,
+    pub(crate) on_dismiss: Callback<XMenuDismiss>,
+}
+
+/// Requests made by the items to their menu
+#[doc(hidden)]
+pub enum XMenuRequest {
+    /// Expand the submenu of the given item. Second value is whether to focus the first item
+    /// of the submenu.
+    Expand(Element, bool),
+    Collapse,
+    Dismiss(XMenuDismiss),
+    /// Check the given item of a radio group, unchecking the other items of the group
+    Check(String, Element),
+}
+
+/// Provided by `XMenu` to its items
+#[doc(hidden)]
+#[derive(Clone, PartialEq)]
+pub struct XMenuContext {
+    /// Item which is currently showing its submenu
+    pub(crate) expanded: Option<Element>,
+    /// Whether the first item of the expanded submenu should be focused
+    pub(crate) focus_expanded: bool,
+    /// Items are placed in a `XMenuBar` rather than a `XMenu`
+    pub(crate) menubar: bool,
+    /// The only item reachable with the Tab key
+    pub(crate) tab_stop: Option<Element>,
+    /// Checked item of each radio group
+    pub(crate) checked: HashMap<String, Element>,
+    pub(crate) on_request: Callback<XMenuRequest>,
+}
+
+#[doc(hidden)]
+pub enum XMenuMessage {
+    OpenAnimationFinished(u32),
+    CloseAnimationFinished(u32),
+    OpenerUpdated(XMenuOpenerContext),
+    Request(XMenuRequest),
+    PointerMove(PointerEvent),
+    PointerLeave(PointerEvent),
+    HoverSettled(u32),
+    KeyDown(KeyboardEvent),
+    FocusIn(FocusEvent),
+    TabStopChanged(Option<Element>),
+}
+
+/// Resolved position of an opened menu
+#[derive(PartialEq, Clone)]
+pub(crate) struct XMenuPosition {
+    left: f64,
+    top: f64,
+    max_width: Option<f64>,
+    max_height: Option<f64>,
+    transform_origin: &'static str,
+}
+
+/// XMenu component
+pub struct XMenu {
+    node_ref: NodeRef,
+    /// Current placement given by the `open` prop or the opener
+    open: Option<XMenuOpen>,
+    /// Menu is displayed (opened or running a transition)
+    opened: bool,
+    animating: bool,
+    /// Menu is opened, but not positioned yet
+    pending_open: Option<XMenuOpen>,
+    position: Option<XMenuPosition>,
+    /// Used to ignore finished messages of interrupted animations
+    animation_id: u32,
+    animation: Option<web_sys::Animation>,
+    opener: Option<XMenuOpenerContext>,
+    _opener_listener: Option<ContextHandle<XMenuOpenerContext>>,
+    on_request: Callback<XMenuRequest>,
+    expanded: Option<Element>,
+    focus_expanded: bool,
+    hovered: Option<Element>,
+    pointer: Option<(f64, f64)>,
+    /// Used to ignore delayed hover checks after the pointer moved again
+    hover_id: u32,
+    tab_stop: Option<Element>,
+    checked: HashMap<String, Element>,
+    /// Characters typed for the type-ahead search and the time of the last key press
+    type_ahead: (String, f64),
+}
+
+impl XMenu {
+    fn current_open(&self, ctx: &yew::Context<Self>) -> Option<XMenuOpen> {
+        ctx.props()
+            .open
+            .clone()
+            .or_else(|| self.opener.as_ref().and_then(|o| o.open.clone()))
+    }
+
+    fn sync_open(&mut self, ctx: &yew::Context<Self>) {
+        let open = self.current_open(ctx);
+        if open == self.open {
+            return;
+        }
+        self.open = open.clone();
+        match open {
+            Some(open) => self.start_open(ctx, open),
+            None => {
+                if self.opened {
+                    self.start_close(ctx);
+                }
+            }
+        }
+    }
+
+    fn dismiss(&self, ctx: &yew::Context<Self>, reason: XMenuDismiss) {
+        if let Some(opener) = &self.opener {
+            opener.on_dismiss.emit(reason);
+        }
+        if let Some(on_dismiss) = ctx.props().on_dismiss.clone() {
+            on_dismiss.emit(reason);
+        }
+    }
+
+    fn stop_animation(&mut self) {
+        self.animation_id += 1;
+        if let Some(animation) = self.animation.take() {
+            animation.finish().ok();
+        }
+    }
+
+    fn start_open(&mut self, ctx: &yew::Context<Self>, open: XMenuOpen) {
+        self.stop_animation();
+        self.opened = true;
+        self.animating = false;
+        self.pending_open = Some(open.clone());
+
+        if let Some(on_open) = ctx.props().on_open.clone() {
+            on_open.emit(open);
+        }
+    }
+
+    fn start_close(&mut self, ctx: &yew::Context<Self>) {
+        self.stop_animation();
+        self.pending_open = None;
+        self.expanded = None;
+        self.hovered = None;
+        self.hover_id += 1;
+
+        if let Some(on_close) = ctx.props().on_close.clone() {
+            on_close.emit(());
+        }
+
+        let transition = &ctx.props().close_transition;
+        let element = self.node_ref.cast::<Element>();
+        match (element, transition_keyframes(transition, false)) {
+            (Some(element), Some(keyframes)) => {
+                self.animating = true;
+                let animation = new_animation(
+                    &element,
+                    &keyframes,
+                    transition.duration,
+                    &transition.easing,
+                );
+                let id = self.animation_id;
+                self.animation = Some(animation.clone());
+                ctx.link().send_future(async move {
+                    JsFuture::from(animation.finished().unwrap()).await.ok();
+                    XMenuMessage::CloseAnimationFinished(id)
+                });
+            }
+            _ => {
+                ctx.link()
+                    .send_message(XMenuMessage::CloseAnimationFinished(self.animation_id));
+            }
+        }
+    }
+
+    /// Measures the menu and the anchors, then applies the resolved position directly to the
+    /// element so the menu is never painted at a wrong place.
+    fn apply_position(&mut self, ctx: &yew::Context<Self>, open: &XMenuOpen) {
+        if let Some(element) = self.node_ref.cast::<HtmlElement>() {
+            self.position = Some(position_popup(&element, open, &ctx.props().align));
+        }
+    }
+
+    /// Finds the item of this menu under the pointer. Second value is `false` when the pointer
+    /// is over the submenu of the item rather than the item itself.
+    fn item_at(&self, target: &Element) -> Option<(Element, bool)> {
+        let element = self.node_ref.cast::<Element>()?;
+        if let Some(expanded) = &self.expanded {
+            if let Some(submenu) = expanded.query_selector(":scope > .x-menu").ok().flatten() {
+                if submenu.contains(Some(target)) {
+                    return Some((expanded.clone(), false));
+                }
+            }
+        }
+
+        let item = target.closest(".x-menuitem").ok().flatten()?;
+        if item.closest(".x-menu").ok().flatten()? == element {
+            Some((item, true))
+        } else {
+            None
+        }
+    }
+
+    /// Whether the pointer is heading towards the expanded submenu. Checks the last movement
+    /// lies in the triangle made by the previous pointer location and the near edge of the
+    /// submenu.
+    fn towards_submenu(&self, previous: Option<(f64, f64)>, current: (f64, f64)) -> bool {
+        let (previous, expanded) = match (previous, &self.expanded) {
+            (Some(previous), Some(expanded)) => (previous, expanded),
+            _ => return false,
+        };
+        let submenu = match expanded.query_selector(":scope > .x-menu").ok().flatten() {
+            Some(submenu) => submenu.get_bounding_client_rect(),
+            None => return false,
+        };
+
+        let edge = if submenu.left() >= current.0 {
+            submenu.left()
+        } else {
+            submenu.right()
+        };
+
+        point_in_triangle(
+            current,
+            previous,
+            (edge, submenu.top()),
+            (edge, submenu.bottom()),
+        )
+    }
+
+    fn focusable_items(&self) -> Vec<HtmlElement> {
+        match self.node_ref.cast::<Element>() {
+            Some(element) => focusable_items(&element),
+            None => vec![],
+        }
+    }
+
+    fn focus_first_item(&self) {
+        if let Some(item) = self.focusable_items().first() {
+            item.focus().ok();
+        }
+    }
+
+    /// Moves the focus to another item. Expanded submenu is collapsed, because it does not
+    /// belong to the focused item anymore.
+    fn focus_item(&mut self, item: &HtmlElement) -> bool {
+        item.focus().ok();
+        self.expanded.take().is_some()
+    }
+
+    fn navigate(&mut self, e: &KeyboardEvent) -> bool {
+        let items = self.focusable_items();
+        if items.is_empty() {
+            return false;
+        }
+
+        let focused = window()
+            .unwrap()
+            .document()
+            .unwrap()
+            .active_element()
+            .and_then(|active| active.closest(".x-menuitem").ok().flatten());
+        let current = focused.and_then(|focused| {
+            items
+                .iter()
+                .position(|item| AsRef::<Element>::as_ref(item) == &focused)
+        });
+        let last = items.len() - 1;
+
+        let next = match e.key().as_str() {
+            "ArrowDown" => match current {
+                Some(i) if i < last => i + 1,
+                _ => 0,
+            },
+            "ArrowUp" => match current {
+                Some(i) if i > 0 => i - 1,
+                _ => last,
+            },
+            "Home" => 0,
+            "End" => last,
+            key if key.chars().count() == 1 && !e.ctrl_key() && !e.meta_key() && !e.alt_key() => {
+                let now = js_sys::Date::now();
+                if now - self.type_ahead.1 > TYPE_AHEAD_TIMEOUT {
+                    self.type_ahead.0.clear();
+                }
+                self.type_ahead.0.push_str(&key.to_lowercase());
+                self.type_ahead.1 = now;
+
+                // Searching again with the same character cycles over the matching items
+                let search = &self.type_ahead.0;
+                let start = match current {
+                    Some(i) if search.chars().count() == 1 => i + 1,
+                    Some(i) => i,
+                    None => 0,
+                };
+                let found = (0..items.len())
+                    .map(|offset| (start + offset) % items.len())
+                    .find(|i| item_text(&items[*i]).starts_with(search.as_str()));
+                match found {
+                    Some(i) => i,
+                    None => return false,
+                }
+            }
+            _ => return false,
+        };
+
+        e.prevent_default();
+        self.focus_item(&items[next])
+    }
+
+    fn schedule_hover(&mut self, ctx: &yew::Context<Self>, delay: u64) {
+        self.hover_id += 1;
+        let id = self.hover_id;
+        ctx.link().send_future(async move {
+            sleep(Duration::from_millis(delay)).await;
+            XMenuMessage::HoverSettled(id)
+        });
+    }
 }
 
 impl Component for XMenu {
     type Properties = XMenuProps;
-    type Message = ();
+    type Message = XMenuMessage;
 
     fn create(ctx: &yew::Context<Self>) -> Self {
-        XMenu
+        let (opener, _opener_listener) =
+            match ctx.link().context(ctx.link().callback(XMenuMessage::OpenerUpdated)) {
+                Some((opener, listener)) => (Some(opener), Some(listener)),
+                None => (None, None),
+            };
+
+        let mut menu = XMenu {
+            node_ref: NodeRef::default(),
+            open: None,
+            opened: false,
+            animating: false,
+            pending_open: None,
+            position: None,
+            animation_id: 0,
+            animation: None,
+            opener,
+            _opener_listener,
+            on_request: ctx.link().callback(XMenuMessage::Request),
+            expanded: None,
+            focus_expanded: false,
+            hovered: None,
+            pointer: None,
+            hover_id: 0,
+            tab_stop: None,
+            checked: HashMap::new(),
+            type_ahead: (String::new(), 0.0),
+        };
+
+        menu.sync_open(ctx);
+
+        menu
+    }
+
+    fn update(&mut self, ctx: &yew::Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            XMenuMessage::OpenAnimationFinished(id) => {
+                if id != self.animation_id {
+                    return false;
+                }
+                self.animation = None;
+                self.animating = false;
+                if let (Some(on_open_finished), Some(open)) =
+                    (ctx.props().on_open_finished.clone(), self.open.clone())
+                {
+                    on_open_finished.emit(open);
+                }
+                true
+            }
+            XMenuMessage::CloseAnimationFinished(id) => {
+                if id != self.animation_id {
+                    return false;
+                }
+                self.animation = None;
+                self.animating = false;
+                self.opened = false;
+                self.position = None;
+                if let Some(on_close_finished) = ctx.props().on_close_finished.clone() {
+                    on_close_finished.emit(());
+                }
+                true
+            }
+            XMenuMessage::OpenerUpdated(opener) => {
+                let focus_first = opener.focus_first
+                    && !self.opener.as_ref().map(|o| o.focus_first).unwrap_or(false);
+                self.opener = Some(opener);
+                self.sync_open(ctx);
+
+                // Already opened with the pointer, then asked to focus with the keyboard
+                if focus_first && self.opened && self.pending_open.is_none() {
+                    self.focus_first_item();
+                }
+                true
+            }
+            XMenuMessage::Request(request) => match request {
+                XMenuRequest::Expand(item, focus) => {
+                    self.hover_id += 1;
+                    self.hovered = Some(item.clone());
+                    if self.expanded.as_ref() == Some(&item) && self.focus_expanded == focus {
+                        return false;
+                    }
+                    self.expanded = Some(item);
+                    self.focus_expanded = focus;
+                    true
+                }
+                XMenuRequest::Collapse => {
+                    self.hover_id += 1;
+                    self.expanded.take().is_some()
+                }
+                XMenuRequest::Dismiss(reason) => {
+                    self.dismiss(ctx, reason);
+                    false
+                }
+                XMenuRequest::Check(group, item) => {
+                    self.checked.insert(group, item.clone()) != Some(item)
+                }
+            },
+            XMenuMessage::PointerMove(e) => {
+                let current = (e.client_x() as f64, e.client_y() as f64);
+                let previous = self.pointer.replace(current);
+
+                let target = match e.target().and_then(|t| t.dyn_into::<Element>().ok()) {
+                    Some(target) => target,
+                    None => return false,
+                };
+                let (item, direct) = match self.item_at(&target) {
+                    Some((item, direct)) => (Some(item), direct),
+                    None => (None, false),
+                };
+
+                if item == self.hovered {
+                    // Keep the submenu while the pointer is heading towards it
+                    if self.hovered != self.expanded && self.towards_submenu(previous, current) {
+                        self.schedule_hover(ctx, SAFE_TRIANGLE_DELAY);
+                    }
+                    return false;
+                }
+
+                if direct {
+                    if let Some(item) = item.clone() {
+                        item.dyn_into::<HtmlElement>().unwrap().focus().ok();
+                    }
+                }
+
+                self.hovered = item;
+                if self.hovered == self.expanded {
+                    self.hover_id += 1;
+                } else if self.towards_submenu(previous, current) {
+                    self.schedule_hover(ctx, SAFE_TRIANGLE_DELAY);
+                } else {
+                    self.schedule_hover(ctx, SUBMENU_OPEN_DELAY);
+                }
+                false
+            }
+            XMenuMessage::PointerLeave(_) => {
+                self.hover_id += 1;
+                self.hovered = None;
+                self.pointer = None;
+                false
+            }
+            XMenuMessage::HoverSettled(id) => {
+                if id != self.hover_id {
+                    return false;
+                }
+
+                let expanded = self.hovered.clone().filter(|item| {
+                    !item.matches(".disabled").unwrap_or(false)
+                        && item
+                            .query_selector(":scope > .x-menu")
+                            .ok()
+                            .flatten()
+                            .is_some()
+                });
+                if expanded == self.expanded {
+                    return false;
+                }
+                self.expanded = expanded;
+                self.focus_expanded = false;
+                true
+            }
+            XMenuMessage::KeyDown(e) => {
+                let element = self.node_ref.cast::<Element>();
+                let target_menu = e
+                    .target()
+                    .and_then(|t| t.dyn_into::<Element>().ok())
+                    .and_then(|t| t.closest(".x-menu").ok().flatten());
+                if element.is_none() || target_menu != element {
+                    return false;
+                }
+
+                match e.key().as_str() {
+                    "Escape" => {
+                        e.prevent_default();
+                        self.dismiss(ctx, XMenuDismiss::Escape);
+                        false
+                    }
+                    "ArrowLeft" if self.opener.as_ref().map(|o| o.submenu).unwrap_or(false) => {
+                        e.prevent_default();
+                        self.dismiss(ctx, XMenuDismiss::Collapse);
+                        false
+                    }
+                    _ => self.navigate(&e),
+                }
+            }
+            XMenuMessage::FocusIn(e) => {
+                let element = self.node_ref.cast::<Element>();
+                let item = e
+                    .target()
+                    .and_then(|t| t.dyn_into::<Element>().ok())
+                    .and_then(|t| t.closest(".x-menuitem").ok().flatten())
+                    .filter(|item| item.closest(".x-menu").ok().flatten() == element);
+                if item.is_none() || item == self.tab_stop {
+                    return false;
+                }
+                self.tab_stop = item;
+                true
+            }
+            XMenuMessage::TabStopChanged(tab_stop) => {
+                self.tab_stop = tab_stop;
+                true
+            }
+        }
+    }
+
+    fn changed(&mut self, ctx: &yew::Context<Self>, _old_props: &Self::Properties) -> bool {
+        self.sync_open(ctx);
+        true
+    }
+
+    fn rendered(&mut self, ctx: &yew::Context<Self>, _first_render: bool) {
+        if self.opened {
+            // Keep the tab stop on an item which still can be focused
+            let items = self.focusable_items();
+            let valid = items
+                .iter()
+                .any(|item| Some(AsRef::<Element>::as_ref(item)) == self.tab_stop.as_ref());
+            if !valid {
+                let first = items.first().map(|item| Element::from(item.clone()));
+                if first != self.tab_stop {
+                    ctx.link().send_message(XMenuMessage::TabStopChanged(first));
+                }
+            }
+        }
+
+        if let Some(open) = self.pending_open.take() {
+            self.apply_position(ctx, &open);
+
+            let element = self.node_ref.cast::<Element>().unwrap();
+            if self.opener.as_ref().map(|o| o.focus_first).unwrap_or(false) {
+                self.focus_first_item();
+            }
+
+            let transition = &ctx.props().open_transition;
+            if let Some(keyframes) = transition_keyframes(transition, true) {
+                self.animating = true;
+                let animation = new_animation(
+                    &element,
+                    &keyframes,
+                    transition.duration,
+                    &transition.easing,
+                );
+                let id = self.animation_id;
+                self.animation = Some(animation.clone());
+                ctx.link().send_future(async move {
+                    JsFuture::from(animation.finished().unwrap()).await.ok();
+                    XMenuMessage::OpenAnimationFinished(id)
+                });
+            } else {
+                ctx.link()
+                    .send_message(XMenuMessage::OpenAnimationFinished(self.animation_id));
+            }
+        }
     }
 
     fn view(&self, ctx: &yew::Context<Self>) -> yew::Html {
+        let mut classes = String::from("x-menu");
+
+        if self.opened {
+            classes.push_str(" opened");
+        }
+
+        if self.animating {
+            classes.push_str(" animating");
+        }
+
+        let style = match &self.position {
+            Some(position) if self.opened => position_style(position),
+            _ => String::new(),
+        };
+
+        let context = XMenuContext {
+            expanded: self.expanded.clone(),
+            focus_expanded: self.focus_expanded,
+            menubar: false,
+            tab_stop: self.tab_stop.clone(),
+            checked: self.checked.clone(),
+            on_request: self.on_request.clone(),
+        };
+
         html! {
-            <div class="x-menu">
+            <div
+                ref={self.node_ref.clone()}
+                role="menu"
+                tabindex="-1"
+                aria-hidden={if self.opened {"false"} else {"true"}}
+                onpointermove={ctx.link().callback(XMenuMessage::PointerMove)}
+                onpointerleave={ctx.link().callback(XMenuMessage::PointerLeave)}
+                onkeydown={ctx.link().callback(XMenuMessage::KeyDown)}
+                onfocusin={ctx.link().callback(XMenuMessage::FocusIn)}
+                {style}
+                class={classes}>
+                <ContextProvider<XMenuContext> {context}>
+                    {for ctx.props().children.iter()}
+                </ContextProvider<XMenuContext>>
             </div>
         }
     }
 }
+
+/// Measures the popup (a menu or a popover) and the anchors, then applies the resolved position
+/// directly to the element.
+pub(crate) fn position_popup(
+    element: &HtmlElement,
+    open: &XMenuOpen,
+    align: &XMenuAlign,
+) -> XMenuPosition {
+    element
+        .set_attribute("style", "position: fixed; left: 0px; top: 0px;")
+        .unwrap();
+    let rect = element.get_bounding_client_rect();
+
+    let position = calculate_position(element, rect, open, align);
+    element
+        .set_attribute("style", &position_style(&position))
+        .unwrap();
+    position
+}
+
+pub(crate) fn position_style(position: &XMenuPosition) -> String {
+    let mut style = format!(
+        "position: fixed; left: {}px; top: {}px; transform-origin: {};",
+        position.left.round(),
+        position.top.round(),
+        position.transform_origin
+    );
+    if let Some(max_width) = position.max_width {
+        write!(style, " max-width: {}px;", max_width.floor()).unwrap();
+    }
+    if let Some(max_height) = position.max_height {
+        write!(style, " max-height: {}px;", max_height.floor()).unwrap();
+    }
+    style
+}
+
+/// Items placed directly inside the given menu (or menubar) which can be focused, in the
+/// document order
+pub(crate) fn focusable_items(element: &Element) -> Vec<HtmlElement> {
+    let items = element
+        .query_selector_all(":scope > .x-menuitem:not(.disabled):not(.hidden)")
+        .unwrap();
+
+    (0..items.length())
+        .filter_map(|i| items.item(i))
+        .filter_map(|node| node.dyn_into::<HtmlElement>().ok())
+        .collect()
+}
+
+/// Text used by the type-ahead search
+fn item_text(item: &Element) -> String {
+    let label = item
+        .query_selector(".x-label")
+        .ok()
+        .flatten()
+        .unwrap_or_else(|| item.clone());
+    label
+        .text_content()
+        .unwrap_or_default()
+        .trim()
+        .to_lowercase()
+}
+
+fn point_in_triangle(p: (f64, f64), a: (f64, f64), b: (f64, f64), c: (f64, f64)) -> bool {
+    let sign = |p1: (f64, f64), p2: (f64, f64), p3: (f64, f64)| {
+        (p1.0 - p3.0) * (p2.1 - p3.1) - (p2.0 - p3.0) * (p1.1 - p3.1)
+    };
+    let d1 = sign(p, a, b);
+    let d2 = sign(p, b, c);
+    let d3 = sign(p, c, a);
+
+    let has_negative = d1 < 0.0 || d2 < 0.0 || d3 < 0.0;
+    let has_positive = d1 > 0.0 || d2 > 0.0 || d3 > 0.0;
+    !(has_negative && has_positive)
+}
+
+fn transition_keyframes(transition: &Transition, opening: bool) -> Option<Object> {
+    if transition.duration <= 0.0 {
+        return None;
+    }
+
+    let frames = match transition.property.as_str() {
+        "transform" => ("transform", ["scale(1, 0)", "none"]),
+        "opacity" => ("opacity", ["0", "1"]),
+        _ => return None,
+    };
+
+    if opening {
+        Some(property_keyframes(frames.0, &frames.1))
+    } else {
+        Some(property_keyframes(frames.0, &[frames.1[1], frames.1[0]]))
+    }
+}
+
+fn calculate_position(
+    menu_element: &Element,
+    menu_rect: web_sys::DomRect,
+    open: &XMenuOpen,
+    align: &XMenuAlign,
+) -> XMenuPosition {
+    let menu_width = menu_rect.width();
+    let menu_height = menu_rect.height();
+    let boundary = window_rect();
+    let alignment = match align {
+        XMenuAlign::Start => Alignment::Start,
+        XMenuAlign::End => Alignment::End,
+    };
+
+    // Menu drawn over the anchor, only moved inside the window
+    let over = |anchor: Option<Element>, inner: Option<Element>| match (anchor, inner) {
+        (Some(anchor), Some(inner)) => {
+            let anchor_rect = anchor.get_bounding_client_rect();
+            let inner_rect = inner.get_bounding_client_rect();
+            let floating = Rect::new(
+                anchor_rect.left() - (inner_rect.left() - menu_rect.left()),
+                anchor_rect.top() - (inner_rect.top() - menu_rect.top()),
+                menu_width,
+                menu_height,
+            );
+            let (left, top, max_height) = contain(floating, &boundary);
+            XMenuPosition {
+                left,
+                top,
+                max_width: None,
+                max_height,
+                transform_origin: "50% 50%",
+            }
+        }
+        _ => XMenuPosition {
+            left: boundary.x,
+            top: boundary.y,
+            max_width: None,
+            max_height: None,
+            transform_origin: "0 0",
+        },
+    };
+
+    let (anchor, options) = match open {
+        XMenuOpen::OverElement(under_ref, over_ref) => {
+            return over(under_ref.cast::<Element>(), over_ref.cast::<Element>());
+        }
+        XMenuOpen::OverLabel(under_ref) => {
+            let label = menu_element
+                .query_selector(".x-menuitem.toggled .x-label")
+                .ok()
+                .flatten()
+                .or_else(|| menu_element.query_selector(".x-menuitem .x-label").ok().flatten());
+            return over(under_ref.cast::<Element>(), label);
+        }
+        XMenuOpen::NextToElement(element_ref) => {
+            let element = match element_ref.cast::<Element>() {
+                Some(element) => element,
+                None => return over(None, None),
+            };
+            // Align the first item of the menu with the element
+            let padding_top = window()
+                .unwrap()
+                .get_computed_style(menu_element)
+                .ok()
+                .flatten()
+                .and_then(|s| s.get_property_value("padding-top").ok())
+                .and_then(|p| p.trim_end_matches("px").parse::<f64>().ok())
+                .unwrap_or(0.0);
+            let mut anchor = client_rect(&element);
+            anchor.y -= padding_top;
+            let side = match align {
+                XMenuAlign::Start => Side::Right,
+                XMenuAlign::End => Side::Left,
+            };
+            let options = PositionOptions::new(side, Alignment::Start, boundary)
+                .flip(&[side.opposite()])
+                .shift();
+            (anchor, options)
+        }
+        XMenuOpen::BelowElement(element_ref) => {
+            let element = match element_ref.cast::<Element>() {
+                Some(element) => element,
+                None => return over(None, None),
+            };
+            let options = PositionOptions::new(Side::Bottom, alignment, boundary)
+                .flip(&[Side::Top])
+                .shift()
+                .constrain_size();
+            (client_rect(&element), options)
+        }
+        XMenuOpen::AtPoint(x, y) => {
+            let options = PositionOptions::new(Side::Bottom, alignment, boundary)
+                .flip(&[Side::Top])
+                .shift()
+                .constrain_size();
+            (Rect::new(*x, *y, 0.0, 0.0), options)
+        }
+    };
+
+    let position = compute_position(anchor, menu_width, menu_height, &options);
+    // Menu grows from the corner nearest to the anchor
+    let transform_origin = match (position.side, position.alignment) {
+        (Side::Left, _) => "100% 0",
+        (Side::Top, Alignment::End) => "100% 100%",
+        (Side::Top, _) => "0 100%",
+        (Side::Bottom, Alignment::End) => "100% 0",
+        _ => "0 0",
+    };
+    XMenuPosition {
+        left: position.x,
+        top: position.y,
+        max_width: position.max_width,
+        max_height: position.max_height,
+        transform_origin,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::point_in_triangle;
+
+    const A: (f64, f64) = (0.0, 0.0);
+    const B: (f64, f64) = (10.0, -10.0);
+    const C: (f64, f64) = (10.0, 10.0);
+
+    #[test]
+    fn point_inside_triangle() {
+        assert!(point_in_triangle((5.0, 0.0), A, B, C));
+        assert!(point_in_triangle((9.0, 8.0), A, B, C));
+    }
+
+    #[test]
+    fn point_on_edge_is_inside() {
+        assert!(point_in_triangle((5.0, 5.0), A, B, C));
+        assert!(point_in_triangle(A, A, B, C));
+    }
+
+    #[test]
+    fn point_outside_triangle() {
+        assert!(!point_in_triangle((5.0, 6.0), A, B, C));
+        assert!(!point_in_triangle((-1.0, 0.0), A, B, C));
+        assert!(!point_in_triangle((11.0, 0.0), A, B, C));
+    }
+
+    #[test]
+    fn winding_order_does_not_matter() {
+        assert!(point_in_triangle((5.0, 0.0), A, C, B));
+        assert!(!point_in_triangle((5.0, 6.0), A, C, B));
+    }
+}
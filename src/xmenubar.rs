@@ -0,0 +1,372 @@
+//! `XMenuBar` is a horizontal bar of `XMenuItem` elements, each of them owning a `XMenu`. Menus
+//! are opened by clicking on the items, and the user can switch between the menus by hovering
+//! or with ArrowLeft/ArrowRight keys while a menu is opened.
+//!
+//! Wrap a character of the item label with `XMnemonic` to open the menu with `Alt` and that
+//! character. Mnemonics are underlined while `Alt` is held.
+//!
+//! ```
+//! <XMenuBar>
+//!     <XMenuItem>
+//!         <XLabel><XMnemonic>{"F"}</XMnemonic>{"ile"}</XLabel>
+//!         <XMenu>
+//!             <XMenuItem><XLabel>{"Open"}</XLabel></XMenuItem>
+//!         </XMenu>
+//!     </XMenuItem>
+//! </XMenuBar>
+//! ```
+//!
+//! > Enable `"element-x-menubar"` feature to use this component.
+use std::collections::HashMap;
+use std::fmt::Write;
+
+use wasm_bindgen::JsCast;
+use web_sys::{window, Element, FocusEvent, HtmlElement, KeyboardEvent, Node, PointerEvent};
+use yew::html::ChildrenWithProps;
+use yew::{
+    function_component, html, Callback, Children, Component, ContextHandle, ContextProvider,
+    NodeRef, Properties,
+};
+
+use crate::{
+    calculate_computed_size,
+    utils::EventListener,
+    xcontainer::XContainerContext,
+    xmenu::{focusable_items, XMenuContext, XMenuRequest},
+    xmenuitem::XMenuItem,
+    XComponentSize,
+};
+
+/// Properties for XMenuBar element
+#[derive(PartialEq, Properties)]
+pub struct XMenuBarProps {
+    #[prop_or_default]
+    pub size: Option<XComponentSize>,
+    /// Top level items. Each item should contain a `XMenu`.
+    #[prop_or_default]
+    pub children: ChildrenWithProps<XMenuItem>,
+}
+
+#[doc(hidden)]
+pub enum XMenuBarMessage {
+    ContainerUpdated(XContainerContext),
+    Request(XMenuRequest),
+    KeyDown(KeyboardEvent),
+    PointerOver(PointerEvent),
+    FocusIn(FocusEvent),
+    FocusOut(FocusEvent),
+    TabStopChanged(Option<Element>),
+    WindowKeyDown(KeyboardEvent),
+    WindowKeyUp(KeyboardEvent),
+    WindowBlur,
+}
+
+/// XMenuBar component
+pub struct XMenuBar {
+    node_ref: NodeRef,
+    container_ctx: XContainerContext,
+    _container_ctx_listener: ContextHandle<XContainerContext>,
+    on_request: Callback<XMenuRequest>,
+    /// Item which is currently showing its menu
+    expanded: Option<Element>,
+    focus_expanded: bool,
+    tab_stop: Option<Element>,
+    checked: HashMap<String, Element>,
+    /// `Alt` key is held
+    mnemonics: bool,
+    _window_listeners: Vec<EventListener>,
+}
+
+impl XMenuBar {
+    /// Top level item which contains the given node
+    fn item_of(&self, node: &Node) -> Option<Element> {
+        let element = self.node_ref.cast::<Element>()?;
+        focusable_items(&element)
+            .into_iter()
+            .find(|item| item.contains(Some(node)))
+            .map(Element::from)
+    }
+
+    fn expand(&mut self, item: &HtmlElement, focus: bool) {
+        item.focus().ok();
+        self.expanded = Some(Element::from(item.clone()));
+        self.focus_expanded = focus;
+    }
+}
+
+impl Component for XMenuBar {
+    type Message = XMenuBarMessage;
+    type Properties = XMenuBarProps;
+
+    fn create(ctx: &yew::Context<Self>) -> Self {
+        let (container_ctx, _container_ctx_listener) = ctx
+            .link()
+            .context(ctx.link().callback(XMenuBarMessage::ContainerUpdated))
+            .expect("Container not found");
+
+        let window = window().unwrap();
+        let keydown = {
+            let link = ctx.link().clone();
+            EventListener::new(&window, "keydown", move |e| {
+                link.send_message(XMenuBarMessage::WindowKeyDown(e.unchecked_into()))
+            })
+        };
+        let keyup = {
+            let link = ctx.link().clone();
+            EventListener::new(&window, "keyup", move |e| {
+                link.send_message(XMenuBarMessage::WindowKeyUp(e.unchecked_into()))
+            })
+        };
+        let blur = {
+            let link = ctx.link().clone();
+            EventListener::new(&window, "blur", move |_| {
+                link.send_message(XMenuBarMessage::WindowBlur)
+            })
+        };
+
+        XMenuBar {
+            node_ref: NodeRef::default(),
+            container_ctx,
+            _container_ctx_listener,
+            on_request: ctx.link().callback(XMenuBarMessage::Request),
+            expanded: None,
+            focus_expanded: false,
+            tab_stop: None,
+            checked: HashMap::new(),
+            mnemonics: false,
+            _window_listeners: vec![keydown, keyup, blur],
+        }
+    }
+
+    fn update(&mut self, _ctx: &yew::Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            XMenuBarMessage::ContainerUpdated(container_ctx) => {
+                self.container_ctx = container_ctx;
+                true
+            }
+            XMenuBarMessage::Request(request) => match request {
+                XMenuRequest::Expand(item, focus) => {
+                    self.expanded = Some(item);
+                    self.focus_expanded = focus;
+                    true
+                }
+                XMenuRequest::Collapse | XMenuRequest::Dismiss(_) => {
+                    self.expanded.take().is_some()
+                }
+                XMenuRequest::Check(group, item) => {
+                    self.checked.insert(group, item.clone()) != Some(item)
+                }
+            },
+            XMenuBarMessage::KeyDown(e) => {
+                if e.default_prevented() {
+                    return false;
+                }
+                let step: isize = match e.key().as_str() {
+                    "ArrowLeft" => -1,
+                    "ArrowRight" => 1,
+                    _ => return false,
+                };
+
+                let element = self.node_ref.cast::<Element>().unwrap();
+                let items = focusable_items(&element);
+                if items.is_empty() {
+                    return false;
+                }
+                let active = window().unwrap().document().unwrap().active_element();
+                let current = active
+                    .and_then(|active| items.iter().position(|item| item.contains(Some(&active))));
+                let next = match current {
+                    Some(i) => (i as isize + step).rem_euclid(items.len() as isize) as usize,
+                    None => 0,
+                };
+
+                e.prevent_default();
+                if self.expanded.is_some() {
+                    self.expand(&items[next], true);
+                    true
+                } else {
+                    items[next].focus().ok();
+                    false
+                }
+            }
+            XMenuBarMessage::PointerOver(e) => {
+                // Switch between the menus by hovering while a menu is opened
+                if self.expanded.is_none() {
+                    return false;
+                }
+                let element = self.node_ref.cast::<Element>();
+                let item = e
+                    .target()
+                    .and_then(|t| t.dyn_into::<Element>().ok())
+                    .and_then(|t| t.closest(".x-menuitem").ok().flatten())
+                    .filter(|item| item.parent_element() == element)
+                    .filter(|item| !item.matches(".disabled").unwrap_or(false));
+                match item {
+                    Some(item) if Some(&item) != self.expanded.as_ref() => {
+                        self.expand(&item.unchecked_into(), false);
+                        true
+                    }
+                    _ => false,
+                }
+            }
+            XMenuBarMessage::FocusIn(e) => {
+                let element = self.node_ref.cast::<Element>();
+                let item = e
+                    .target()
+                    .and_then(|t| t.dyn_into::<Element>().ok())
+                    .and_then(|t| t.closest(".x-menuitem").ok().flatten())
+                    .filter(|item| item.parent_element() == element);
+                if item.is_none() || item == self.tab_stop {
+                    return false;
+                }
+                self.tab_stop = item;
+                true
+            }
+            XMenuBarMessage::FocusOut(e) => {
+                // Close the opened menu when the focus leaves the menubar (Eg:- clicked outside)
+                let element = self.node_ref.cast::<Element>().unwrap();
+                let inside = e
+                    .related_target()
+                    .and_then(|t| t.dyn_into::<Node>().ok())
+                    .map(|node| element.contains(Some(&node)))
+                    .unwrap_or(false);
+                if inside {
+                    return false;
+                }
+                self.expanded.take().is_some()
+            }
+            XMenuBarMessage::TabStopChanged(tab_stop) => {
+                self.tab_stop = tab_stop;
+                true
+            }
+            XMenuBarMessage::WindowKeyDown(e) => {
+                if e.key() == "Alt" {
+                    let changed = !self.mnemonics;
+                    self.mnemonics = true;
+                    return changed;
+                }
+                if !e.alt_key() || e.ctrl_key() || e.meta_key() {
+                    return false;
+                }
+
+                // Codes are used, because some platforms change the key when `Alt` is held
+                let code = e.code();
+                let character = match code
+                    .strip_prefix("Key")
+                    .or_else(|| code.strip_prefix("Digit"))
+                {
+                    Some(character) => character.to_lowercase(),
+                    None => return false,
+                };
+
+                let element = self.node_ref.cast::<Element>().unwrap();
+                let item = focusable_items(&element).into_iter().find(|item| {
+                    item.query_selector(".x-mnemonic")
+                        .ok()
+                        .flatten()
+                        .and_then(|mnemonic| mnemonic.text_content())
+                        .map(|text| text.trim().to_lowercase() == character)
+                        .unwrap_or(false)
+                });
+                match item {
+                    Some(item) => {
+                        e.prevent_default();
+                        self.expand(&item, true);
+                        true
+                    }
+                    None => false,
+                }
+            }
+            XMenuBarMessage::WindowKeyUp(e) => {
+                if e.key() == "Alt" && self.mnemonics {
+                    self.mnemonics = false;
+                    return true;
+                }
+                false
+            }
+            XMenuBarMessage::WindowBlur => {
+                let changed = self.mnemonics;
+                self.mnemonics = false;
+                changed
+            }
+        }
+    }
+
+    fn rendered(&mut self, ctx: &yew::Context<Self>, _first_render: bool) {
+        // Keep the tab stop on an item which still can be focused
+        if let Some(element) = self.node_ref.cast::<Element>() {
+            let items = focusable_items(&element);
+            let valid = items
+                .iter()
+                .any(|item| Some(AsRef::<Element>::as_ref(item)) == self.tab_stop.as_ref());
+            if !valid {
+                let first = items.first().map(|item| Element::from(item.clone()));
+                if first != self.tab_stop {
+                    ctx.link()
+                        .send_message(XMenuBarMessage::TabStopChanged(first));
+                }
+            }
+        }
+
+        // Expanded item was removed
+        if let Some(expanded) = &self.expanded {
+            if self.item_of(expanded).is_none() {
+                ctx.link()
+                    .send_message(XMenuBarMessage::Request(XMenuRequest::Collapse));
+            }
+        }
+    }
+
+    fn view(&self, ctx: &yew::Context<Self>) -> yew::Html {
+        let props = ctx.props();
+        let mut classes = String::from("x-menubar");
+
+        let computed_size =
+            calculate_computed_size(props.size.clone(), self.container_ctx.size.clone());
+        write!(classes, " computedsize-{}", computed_size.to_string()).unwrap();
+
+        if self.mnemonics {
+            classes.push_str(" mnemonics");
+        }
+
+        let context = XMenuContext {
+            expanded: self.expanded.clone(),
+            focus_expanded: self.focus_expanded,
+            menubar: true,
+            tab_stop: self.tab_stop.clone(),
+            checked: self.checked.clone(),
+            on_request: self.on_request.clone(),
+        };
+
+        html! {
+            <div
+                ref={self.node_ref.clone()}
+                role="menubar"
+                onkeydown={ctx.link().callback(XMenuBarMessage::KeyDown)}
+                onpointerover={ctx.link().callback(XMenuBarMessage::PointerOver)}
+                onfocusin={ctx.link().callback(XMenuBarMessage::FocusIn)}
+                onfocusout={ctx.link().callback(XMenuBarMessage::FocusOut)}
+                class={classes}>
+                <ContextProvider<XMenuContext> {context}>
+                    {for props.children.iter()}
+                </ContextProvider<XMenuContext>>
+            </div>
+        }
+    }
+}
+
+/// Properties for XMnemonic element
+#[derive(PartialEq, Properties)]
+pub struct XMnemonicProps {
+    /// Character used to open the menu
+    #[prop_or_default]
+    pub children: Children,
+}
+
+/// Marks the character of a `XMenuBar` item label which opens the menu with `Alt`
+#[function_component]
+pub fn XMnemonic(props: &XMnemonicProps) -> yew::Html {
+    html! {
+        <span class="x-mnemonic">{for props.children.iter()}</span>
+    }
+}
@@ -1,18 +1,20 @@
-use js_sys::{Math::max, Object};
-use prokio::{spawn_local, time::sleep};
-use serde_wasm_bindgen::to_value;
+use prokio::time::sleep;
 use std::{
-    collections::HashMap,
     fmt::Write,
     time::{Duration, Instant},
 };
 use wasm_bindgen::JsCast;
-use wasm_bindgen_futures::JsFuture;
-use web_sys::{window, Element, FocusEvent, HtmlElement, KeyboardEvent, MouseEvent, PointerEvent};
-use yew::{html, Callback, Children, Component, ContextHandle, NodeRef, Properties};
+use web_sys::{Element, FocusEvent, HtmlElement, KeyboardEvent, MouseEvent, PointerEvent};
+use yew::html::ChildrenRenderer;
+use yew::{html, Callback, Component, ContextHandle, ContextProvider, NodeRef, Properties};
 
 use crate::{
-    calculate_computed_size, utils::new_animation, xcontainer::XContainerContext, XComponentSize,
+    calculate_computed_size,
+    ripples::{RippleMessage, Ripples},
+    utils::min_pressed_time,
+    xcontainer::XContainerContext,
+    xmenu::{XMenuContext, XMenuDismiss, XMenuOpen, XMenuOpenerContext, XMenuRequest},
+    CSSEasing, Transition, XComponentSize,
 };
 
 #[derive(PartialEq, Properties)]
@@ -26,12 +28,18 @@ pub struct XMenuItemProps {
     pub disabled: bool,
     #[prop_or_default]
     pub toggled: bool,
+    /// Name of the radio group of this item. Checking an item unchecks the other items of the
+    /// same group in the menu. Items of a group are always togglable.
+    #[prop_or_default]
+    pub group: Option<String>,
     /// Value associated with this menu item (usually the command name)
     #[prop_or_default]
     pub value: Option<String>,
+    /// Contents of the item. Place a `XMenu` inside to make it a submenu.
     #[prop_or_default]
-    pub children: Children,
-    /// This callback is firing on the click event when component is togglable
+    pub children: ChildrenRenderer<XMenuItemChild>,
+    /// This callback is firing on the click event when component is togglable, with the new
+    /// toggled state. Items of a radio group are only firing it when they become checked.
     #[prop_or_default]
     pub ontoggle: Option<Callback<(MouseEvent, bool)>>,
     /// This callback is firing on the click event even when the component is togglable
@@ -42,40 +50,40 @@ pub struct XMenuItemProps {
     pub on_trigger_end: Callback<()>,
     #[prop_or_default]
     pub trigger_effect: XMenuItemTriggerEffect,
-}
-
-#[derive(PartialEq)]
-enum XMenuItemRippleAnimationStatus {
-    Created,
-    Started,
-    Finished,
-}
-
-enum XMenuItemRippleClass {
-    Click,
-    PointerDown
-}
-
-struct XMenuItemRipple {
-    node_ref: NodeRef,
-    size: f64,
-    top: f64,
-    left: f64,
-    class: XMenuItemRippleClass,
-    in_animation: XMenuItemRippleAnimationStatus,
-    out_animation: XMenuItemRippleAnimationStatus,
+    /// Animation of the ripples spawned by the pointer or clicks. Only the duration and the
+    /// easing are used.
+    #[prop_or(Transition::new("transform", 300.0, CSSEasing::CubicBezier(0.4, 0.0, 0.2, 1.0)))]
+    pub ripple_grow_transition: Transition,
+    /// Animation to fade out the ripples. Only the duration and the easing are used.
+    #[prop_or(Transition::new("opacity", 300.0, CSSEasing::CubicBezier(0.4, 0.0, 0.2, 1.0)))]
+    pub ripple_fade_transition: Transition,
+    /// Key combination which clicks this item anywhere inside the `XContainer`, even when the
+    /// menu is closed. Use a `XShortcut` child to display it.
+    #[cfg(feature = "element-x-shortcut")]
+    #[prop_or_default]
+    pub shortcut: Option<crate::xshortcut::XKeyCombination>,
+    /// When the `shortcut` is already registered by another component. The first registration
+    /// wins, so this item doesn't get the shortcut. Logged to the console when not given.
+    #[cfg(feature = "element-x-shortcut")]
+    #[prop_or_default]
+    pub on_shortcut_conflict: Option<Callback<crate::xshortcut::XShortcutConflict>>,
 }
 
 pub struct XMenuItem {
     focused: bool,
     _container_ctx_listner: ContextHandle<XContainerContext>,
     container_ctx: XContainerContext,
-    ripples_ref: NodeRef,
     root_ref: NodeRef,
     pointer_down: Option<(Instant, PointerEvent)>,
-    ripples: Vec<XMenuItemRipple>,
-    prev_ripples_count: u8,
+    ripples: Ripples,
     pressed: bool,
+    /// Item was clicked and waiting for the trigger effect to finish
+    triggered: bool,
+    menu_ctx: Option<XMenuContext>,
+    _menu_ctx_listener: Option<ContextHandle<XMenuContext>>,
+    on_submenu_dismiss: Callback<XMenuDismiss>,
+    #[cfg(feature = "element-x-shortcut")]
+    shortcut_registration: Option<crate::xshortcut::XShortcutRegistration>,
 }
 
 #[derive(PartialEq)]
@@ -91,7 +99,43 @@ impl Default for XMenuItemTriggerEffect {
     }
 }
 
-pub enum XMenuItemChild {}
+#[derive(PartialEq, Clone, derive_more::From)]
+pub enum XMenuItemChild {
+    Menu(yew::virtual_dom::VChild<crate::xmenu::XMenu>),
+    #[cfg(feature = "element-x-label")]
+    Label(yew::virtual_dom::VChild<crate::xlabel::XLabel>),
+    #[cfg(feature = "element-x-shortcut")]
+    Shortcut(yew::virtual_dom::VChild<crate::xshortcut::XShortcut>),
+    /// Plain text contents (Eg:- `<XMenuItem>{"Open"}</XMenuItem>`)
+    Text(yew::virtual_dom::VText),
+    Other(yew::Html),
+}
+
+impl From<&str> for XMenuItemChild {
+    fn from(text: &str) -> Self {
+        Self::Text(yew::virtual_dom::VText::new(text.to_string()))
+    }
+}
+
+impl From<String> for XMenuItemChild {
+    fn from(text: String) -> Self {
+        Self::Text(yew::virtual_dom::VText::new(text))
+    }
+}
+
+impl Into<yew::Html> for XMenuItemChild {
+    fn into(self) -> yew::Html {
+        match self {
+            Self::Menu(child) => child.into(),
+            #[cfg(feature = "element-x-label")]
+            Self::Label(child) => child.into(),
+            #[cfg(feature = "element-x-shortcut")]
+            Self::Shortcut(child) => child.into(),
+            Self::Text(child) => child.into(),
+            Self::Other(child) => child.into(),
+        }
+    }
+}
 
 pub enum XMenuItemMessage {
     Focus(FocusEvent),
@@ -101,40 +145,112 @@ pub enum XMenuItemMessage {
     PointerUp(PointerEvent),
     Click(MouseEvent),
     ResetPressed,
-    RippleCreated(u8),
-    RippleInAnimationFinished(u8),
-    RippleOutAnimationFinished(u8),
+    Ripple(RippleMessage),
     Blinking,
+    TriggerEnd,
+    KeyDown(KeyboardEvent),
+    MenuUpdated(XMenuContext),
+    SubmenuDismissed(XMenuDismiss),
+    #[cfg(feature = "element-x-shortcut")]
+    Shortcut(KeyboardEvent),
 }
 
 impl XMenuItem {
-    fn start_ripple_out_animation(&mut self, link: yew::html::Scope<Self>, i: usize) {
-        if let Some(ripple) = self.ripples.get_mut(i) {
-            let element = ripple.node_ref.clone().cast::<Element>().unwrap();
-            let opacity = if let Some(computed_style) =
-                window().unwrap().get_computed_style(&element).unwrap()
-            {
-                computed_style
-                    .get_property_value("opacity")
-                    .unwrap_or(String::from("1"))
-            } else {
-                String::from("1")
-            };
+    fn has_submenu(props: &XMenuItemProps) -> bool {
+        props
+            .children
+            .iter()
+            .any(|child| matches!(child, XMenuItemChild::Menu(_)))
+    }
+
+    fn is_expanded(&self) -> bool {
+        match (&self.menu_ctx, self.root_ref.cast::<Element>()) {
+            (Some(menu_ctx), Some(root_element)) => {
+                menu_ctx.expanded.as_ref() == Some(&root_element)
+            }
+            _ => false,
+        }
+    }
+
+    fn is_togglable(props: &XMenuItemProps) -> bool {
+        props.togglable || props.group.is_some()
+    }
+
+    /// Items of a radio group are checked by their menu once any item of the group is checked
+    fn is_toggled(&self, props: &XMenuItemProps) -> bool {
+        let checked = match (&self.menu_ctx, &props.group) {
+            (Some(menu_ctx), Some(group)) => menu_ctx.checked.get(group),
+            _ => None,
+        };
+        match checked {
+            Some(checked) => self.root_ref.cast::<Element>().as_ref() == Some(checked),
+            None => props.toggled,
+        }
+    }
 
-            ripple.out_animation = XMenuItemRippleAnimationStatus::Started;
-            let mut keyframes = HashMap::new();
-            keyframes.insert("opacity", [&opacity, "0"]);
-            let animation = new_animation(
-                &element,
-                &Object::try_from(&to_value(&keyframes).unwrap()).unwrap(),
-                300.0,
-                &crate::CSSEasing::CubicBezier(0.4, 0.0, 0.2, 1.0),
-            );
-
-            link.send_future(async move {
-                JsFuture::from(animation.finished().unwrap()).await.unwrap();
-                XMenuItemMessage::RippleOutAnimationFinished(i as u8)
-            });
+    /// Makes this item the checked item of its radio group
+    fn check(&self, props: &XMenuItemProps) {
+        if let (Some(menu_ctx), Some(group), Some(root_element)) =
+            (&self.menu_ctx, &props.group, self.root_ref.cast::<Element>())
+        {
+            menu_ctx
+                .on_request
+                .emit(XMenuRequest::Check(group.clone(), root_element));
+        }
+    }
+
+    /// Emits `ontoggle` with the new state when the item is togglable
+    fn toggle(&self, props: &XMenuItemProps, e: &MouseEvent) {
+        if !Self::is_togglable(props) || e.default_prevented() {
+            return;
+        }
+        let toggled = self.is_toggled(props);
+        // Checked item of a radio group can't be unchecked by clicking
+        if props.group.is_none() || !toggled {
+            self.check(props);
+            if let Some(ontoggle) = props.ontoggle.clone() {
+                ontoggle.emit((e.clone(), !toggled));
+            }
+        }
+    }
+
+    #[cfg(feature = "element-x-shortcut")]
+    fn register_shortcut(
+        ctx: &yew::Context<Self>,
+        container_ctx: &XContainerContext,
+    ) -> Option<crate::xshortcut::XShortcutRegistration> {
+        let shortcut = ctx.props().shortcut.clone()?;
+        let callback = ctx.link().callback(XMenuItemMessage::Shortcut);
+        match container_ctx.shortcuts.register(shortcut, callback) {
+            Ok(registration) => Some(registration),
+            Err(conflict) => {
+                match &ctx.props().on_shortcut_conflict {
+                    Some(on_shortcut_conflict) => on_shortcut_conflict.emit(conflict),
+                    None => crate::utils::console_log(conflict.to_string()),
+                }
+                None
+            }
+        }
+    }
+
+    #[cfg(feature = "element-x-shortcut")]
+    fn reregister_shortcut(&mut self, ctx: &yew::Context<Self>) {
+        // Drop the previous registration first, so the same combination can be registered again
+        self.shortcut_registration = None;
+        self.shortcut_registration = Self::register_shortcut(ctx, &self.container_ctx);
+    }
+
+    fn in_menubar(&self) -> bool {
+        self.menu_ctx.as_ref().map(|m| m.menubar).unwrap_or(false)
+    }
+
+    fn expand(&self, focus_submenu: bool) {
+        if let (Some(menu_ctx), Some(root_element)) =
+            (&self.menu_ctx, self.root_ref.cast::<Element>())
+        {
+            menu_ctx
+                .on_request
+                .emit(XMenuRequest::Expand(root_element, focus_submenu));
         }
     }
 }
@@ -149,23 +265,39 @@ impl Component for XMenuItem {
             .context(ctx.link().callback(XMenuItemMessage::ContainerUpdated))
             .expect("Container not found");
 
+        let (menu_ctx, _menu_ctx_listener) =
+            match ctx.link().context(ctx.link().callback(XMenuItemMessage::MenuUpdated)) {
+                Some((menu_ctx, listener)) => (Some(menu_ctx), Some(listener)),
+                None => (None, None),
+            };
+
         XMenuItem {
             focused: false,
             _container_ctx_listner,
-            container_ctx,
             root_ref: NodeRef::default(),
-            ripples_ref: NodeRef::default(),
             pointer_down: None,
-            ripples: Vec::new(),
-            prev_ripples_count: 0,
+            ripples: Ripples::new(ctx.link().callback(XMenuItemMessage::Ripple)),
             pressed: false,
+            triggered: false,
+            menu_ctx,
+            _menu_ctx_listener,
+            on_submenu_dismiss: ctx.link().callback(XMenuItemMessage::SubmenuDismissed),
+            #[cfg(feature = "element-x-shortcut")]
+            shortcut_registration: Self::register_shortcut(ctx, &container_ctx),
+            container_ctx,
         }
     }
 
     fn update(&mut self, ctx: &yew::Context<Self>, msg: Self::Message) -> bool {
         match msg {
             XMenuItemMessage::ContainerUpdated(container_ctx) => {
+                #[cfg(feature = "element-x-shortcut")]
+                let registry_changed = container_ctx.shortcuts != self.container_ctx.shortcuts;
                 self.container_ctx = container_ctx;
+                #[cfg(feature = "element-x-shortcut")]
+                if registry_changed {
+                    self.reregister_shortcut(ctx);
+                }
             }
             XMenuItemMessage::Focus(_) => {
                 self.focused = true;
@@ -205,46 +337,21 @@ impl Component for XMenuItem {
                 self.pointer_down = Some((Instant::now(), e.clone()));
                 self.pressed = true;
 
-                // TODO: If do not have a XMenu as a child
-                match ctx.props().trigger_effect {
-                    XMenuItemTriggerEffect::Ripple => {
-                        let ripples_element = self.ripples_ref.cast::<HtmlElement>().unwrap();
-                        let bounding_box = ripples_element.get_bounding_client_rect();
-                        let size = max(bounding_box.width(), bounding_box.height()) * 1.5;
-                        let top = e.client_y() as f64 - bounding_box.y() - size / 2.0;
-                        let left = e.client_x() as f64 - bounding_box.x() - size / 2.0;
-                        self.ripples.push(XMenuItemRipple {
-                            node_ref: NodeRef::default(),
-                            size,
-                            top,
-                            left,
-                            in_animation: XMenuItemRippleAnimationStatus::Created,
-                            out_animation: XMenuItemRippleAnimationStatus::Created,
-                            class: XMenuItemRippleClass::PointerDown,
-                        });
-                    }
-                    _ => {}
+                if ctx.props().trigger_effect == XMenuItemTriggerEffect::Ripple
+                    && !Self::has_submenu(ctx.props())
+                {
+                    self.ripples.press(&e);
                 }
             }
             XMenuItemMessage::PointerUp(_) => {
                 if let Some(pointer_down) = self.pointer_down.clone() {
                     self.pointer_down = None;
-
-                    let found_ripple = self.ripples.iter().position(|r| {
-                        r.in_animation == XMenuItemRippleAnimationStatus::Finished
-                            && r.out_animation == XMenuItemRippleAnimationStatus::Created
-                    });
-                    if let Some(in_ripple) = found_ripple {
-                        self.start_ripple_out_animation(ctx.link().clone(), in_ripple);
-                    }
+                    self.ripples.release(&ctx.props().ripple_fade_transition);
 
                     ctx.link().send_future(async move {
                         let pressed_time = Instant::now() - pointer_down.0;
-                        let min_pressed_time = if pointer_down.1.pointer_type() == "touch" {
-                            600
-                        } else {
-                            150
-                        } as u128;
+                        let min_pressed_time =
+                            min_pressed_time(&pointer_down.1.pointer_type()) as u128;
 
                         if pressed_time.as_millis() < min_pressed_time {
                             sleep(Duration::from_millis(
@@ -257,39 +364,12 @@ impl Component for XMenuItem {
                     });
                 }
             }
-            XMenuItemMessage::RippleCreated(i) => {
-                let ripple = self.ripples.get_mut(i as usize);
-                if let Some(ripple) = ripple {
-                    let ripple_element = ripple.node_ref.cast::<Element>().unwrap();
-                    let mut keyframes = HashMap::new();
-                    keyframes.insert("transform", ["scale3d(0, 0, 0)", "none"]);
-                    let animation = new_animation(
-                        &ripple_element,
-                        &Object::try_from(&to_value(&keyframes).unwrap()).unwrap(),
-                        300.0,
-                        &crate::CSSEasing::CubicBezier(0.4, 0.0, 0.2, 1.0),
-                    );
-                    ripple.in_animation = XMenuItemRippleAnimationStatus::Started;
-                    ctx.link().send_future(async move {
-                        JsFuture::from(animation.finished().unwrap()).await.unwrap();
-                        XMenuItemMessage::RippleInAnimationFinished(i)
-                    });
-                }
-            }
-            XMenuItemMessage::RippleInAnimationFinished(i) => {
-                if let Some(ripple) = self.ripples.get_mut(i as usize) {
-                    ripple.in_animation = XMenuItemRippleAnimationStatus::Finished;
-                    if let None = self.pointer_down {
-                        self.start_ripple_out_animation(ctx.link().clone(), i as usize);
-                    }
-                }
-            }
-            XMenuItemMessage::RippleOutAnimationFinished(i) => {
-                self.ripples.remove(i as usize);
-
-                if self.ripples.len() == 0 {
-                    ctx.props().on_trigger_end.emit(());
+            XMenuItemMessage::Ripple(msg) => {
+                let finished = self.ripples.update(msg, &ctx.props().ripple_fade_transition);
+                if finished && self.ripples.is_empty() {
+                    ctx.link().send_message(XMenuItemMessage::TriggerEnd);
                 }
+                return finished;
             }
             XMenuItemMessage::Click(e) => {
                 let event_target = e.target().map(|t| t.dyn_into::<Element>().unwrap());
@@ -297,10 +377,14 @@ impl Component for XMenuItem {
                 if e.buttons() > 1 {
                     return false;
                 }
+                if ctx.props().disabled {
+                    return false;
+                }
                 if let Some(event_target) = event_target {
+                    let root_element = self.root_ref.cast::<Element>().unwrap();
                     let closest_item = event_target.closest(".x-menuitem").unwrap();
                     if let Some(closest_item) = closest_item {
-                        if closest_item != event_target {
+                        if closest_item != root_element {
                             return false;
                         }
                     }
@@ -319,31 +403,28 @@ impl Component for XMenuItem {
 
                 // TODO: Check if a x-menuitem exist in closing menu
 
-                if ctx.props().togglable {
-                    if !e.default_prevented() {
-                        if let Some(ontoggle) = ctx.props().ontoggle.clone() {
-                            ontoggle.emit((e.clone(), !ctx.props().togglable));
+                if Self::has_submenu(ctx.props()) {
+                    // Menus of a menubar are toggled by clicking
+                    if self.in_menubar() && self.is_expanded() {
+                        if let Some(menu_ctx) = &self.menu_ctx {
+                            menu_ctx.on_request.emit(XMenuRequest::Collapse);
                         }
+                    } else {
+                        self.expand(false);
                     }
+                    return true;
+                }
+
+                if let Some(onclick) = ctx.props().onclick.clone() {
+                    onclick.emit(e.clone());
                 }
 
-                // TODO: If do not have a XMenu as a child
+                self.toggle(ctx.props(), &e);
+
+                self.triggered = true;
                 match ctx.props().trigger_effect {
                     XMenuItemTriggerEffect::Ripple => {
-                        let ripples_element = self.ripples_ref.cast::<HtmlElement>().unwrap();
-                        let bounding_box = ripples_element.get_bounding_client_rect();
-                        let size = max(bounding_box.width(), bounding_box.height()) * 1.5;
-                        let top = e.client_y() as f64 - bounding_box.y() - size / 2.0;
-                        let left = e.client_x() as f64 - bounding_box.x() - size / 2.0;
-                        self.ripples.push(XMenuItemRipple {
-                            node_ref: NodeRef::default(),
-                            size,
-                            top,
-                            left,
-                            in_animation: XMenuItemRippleAnimationStatus::Created,
-                            out_animation: XMenuItemRippleAnimationStatus::Created,
-                            class: XMenuItemRippleClass::Click,
-                        });
+                        self.ripples.click(&e);
                     }
                     XMenuItemTriggerEffect::Blink => {
                         if self.focused {
@@ -370,10 +451,9 @@ impl Component for XMenuItem {
                         }
                     }
                     XMenuItemTriggerEffect::None => {
-                        let callback = ctx.props().on_trigger_end.clone();
-                        spawn_local(async move {
+                        ctx.link().send_future(async move {
                             sleep(Duration::from_millis(50)).await;
-                            callback.emit(());
+                            XMenuItemMessage::TriggerEnd
                         });
                     }
                 }
@@ -386,43 +466,119 @@ impl Component for XMenuItem {
                         let parent_element = parent_element.dyn_into::<HtmlElement>().unwrap();
                         parent_element.focus().unwrap();
                         self.focused = false;
-                        let callback = ctx.props().on_trigger_end.clone();
-                        spawn_local(async move {
+                        ctx.link().send_future(async move {
                             sleep(Duration::from_millis(150)).await;
-                            callback.emit(());
+                            XMenuItemMessage::TriggerEnd
                         });
                     }
                 } else {
                     let root_element = self.root_ref.cast::<HtmlElement>().unwrap();
                     root_element.focus().unwrap();
                     self.focused = true;
-                    let callback = ctx.props().on_trigger_end.clone();
-                    spawn_local(async move {
+                    ctx.link().send_future(async move {
                         sleep(Duration::from_millis(150)).await;
-                        callback.emit(())
+                        XMenuItemMessage::TriggerEnd
                     });
                 }
             }
+            XMenuItemMessage::TriggerEnd => {
+                ctx.props().on_trigger_end.emit(());
+                if self.triggered {
+                    self.triggered = false;
+                    if let Some(menu_ctx) = &self.menu_ctx {
+                        menu_ctx
+                            .on_request
+                            .emit(XMenuRequest::Dismiss(XMenuDismiss::Triggered));
+                    }
+                }
+                return false;
+            }
+            XMenuItemMessage::KeyDown(e) => {
+                let root_element = self.root_ref.cast::<HtmlElement>().unwrap();
+                let closest_item = e
+                    .target()
+                    .and_then(|t| t.dyn_into::<Element>().ok())
+                    .and_then(|t| t.closest(".x-menuitem").ok().flatten());
+                if closest_item.as_ref() != Some(&root_element) {
+                    return false;
+                }
+
+                let has_submenu = Self::has_submenu(ctx.props());
+                let expand_key = if self.in_menubar() {
+                    "ArrowDown"
+                } else {
+                    "ArrowRight"
+                };
+                match e.code().as_str() {
+                    code if has_submenu
+                        && (code == "Enter" || code == "Space" || code == expand_key) =>
+                    {
+                        e.prevent_default();
+                        self.expand(true);
+                    }
+                    "Enter" | "Space" => {
+                        e.prevent_default();
+                        root_element.click();
+                    }
+                    _ => {}
+                }
+                return false;
+            }
+            XMenuItemMessage::MenuUpdated(menu_ctx) => {
+                self.menu_ctx = Some(menu_ctx);
+            }
+            XMenuItemMessage::SubmenuDismissed(reason) => {
+                if let Some(menu_ctx) = &self.menu_ctx {
+                    match reason {
+                        XMenuDismiss::Triggered => {
+                            menu_ctx.on_request.emit(XMenuRequest::Dismiss(reason));
+                        }
+                        XMenuDismiss::Escape | XMenuDismiss::Collapse => {
+                            menu_ctx.on_request.emit(XMenuRequest::Collapse);
+                            let root_element = self.root_ref.cast::<HtmlElement>().unwrap();
+                            root_element.focus().unwrap();
+                        }
+                    }
+                }
+                return false;
+            }
+            #[cfg(feature = "element-x-shortcut")]
+            XMenuItemMessage::Shortcut(e) => {
+                let props = ctx.props();
+                if props.disabled || Self::has_submenu(props) {
+                    return false;
+                }
+                let click = MouseEvent::new("click").unwrap();
+                if let Some(onclick) = props.onclick.clone() {
+                    onclick.emit(click.clone());
+                }
+                self.toggle(props, &click);
+                e.prevent_default();
+                return false;
+            }
         }
         true
     }
 
-    fn rendered(&mut self, ctx: &yew::Context<Self>, _first_render: bool) {
-        if self.prev_ripples_count as usize > self.ripples.len() {
-            let link = ctx.link().clone();
-
-            if let Some(pointer_down) = self.pointer_down.clone() {
-                let root_element = self.root_ref.cast::<HtmlElement>().unwrap();
-                root_element
-                    .set_pointer_capture(pointer_down.1.pointer_id())
-                    .unwrap();
-            }
+    fn changed(&mut self, ctx: &yew::Context<Self>, old_props: &Self::Properties) -> bool {
+        let props = ctx.props();
+        if props.toggled && (!old_props.toggled || props.group != old_props.group) {
+            self.check(props);
+        }
+        #[cfg(feature = "element-x-shortcut")]
+        if props.shortcut != old_props.shortcut {
+            self.reregister_shortcut(ctx);
+        }
+        true
+    }
 
-            for i in self.prev_ripples_count..self.ripples.len() as u8 {
-                link.send_message(XMenuItemMessage::RippleCreated(i));
-            }
-            self.prev_ripples_count = self.ripples.len() as u8;
+    fn rendered(&mut self, ctx: &yew::Context<Self>, first_render: bool) {
+        if first_render && ctx.props().toggled {
+            self.check(ctx.props());
         }
+
+        self.ripples
+            .rendered(&ctx.props().ripple_grow_transition);
     }
 
     fn view(&self, ctx: &yew::Context<Self>) -> yew::Html {
@@ -433,11 +589,13 @@ impl Component for XMenuItem {
             calculate_computed_size(props.size.clone(), self.container_ctx.size.clone());
         write!(classes, " computedsize-{}", computed_size.to_string()).unwrap();
 
-        if props.togglable {
+        let togglable = Self::is_togglable(props);
+        if togglable {
             classes.write_str(" togglable").unwrap();
         }
 
-        if props.toggled {
+        let toggled = self.is_toggled(props);
+        if toggled {
             classes.write_str(" toggled").unwrap();
         }
 
@@ -445,21 +603,52 @@ impl Component for XMenuItem {
             classes.write_str(" pressed").unwrap();
         }
 
+        if props.disabled {
+            classes.write_str(" disabled").unwrap();
+        }
+
+        let has_submenu = Self::has_submenu(props);
+        let expanded = has_submenu && self.is_expanded();
+        if expanded {
+            classes.write_str(" expanded").unwrap();
+        }
+
         let onfocus = ctx.link().callback(XMenuItemMessage::Focus);
         let onblur = ctx.link().callback(XMenuItemMessage::Blur);
         let onpointerup = ctx.link().callback(XMenuItemMessage::PointerUp);
         let onpointerdown = ctx.link().callback(XMenuItemMessage::PointerDown);
         let onclick = ctx.link().callback(XMenuItemMessage::Click);
-        let onkeydown = Callback::from(move |e: KeyboardEvent| {
-            let code = e.code();
-            if code == "Enter" || code == "Space" {
-                e.prevent_default();
-                let event_target = e.target().map(|t| t.dyn_into::<HtmlElement>().unwrap());
-                if let Some(event_target) = event_target {
-                    event_target.click();
+        let onkeydown = ctx.link().callback(XMenuItemMessage::KeyDown);
+
+        // Only one item of a menu is reachable with the Tab key
+        let tabindex = match &self.menu_ctx {
+            _ if props.disabled => "-1",
+            Some(menu_ctx) => {
+                let root_element = self.root_ref.cast::<Element>();
+                if root_element.is_some() && menu_ctx.tab_stop == root_element {
+                    "0"
+                } else {
+                    "-1"
                 }
             }
-        });
+            None => "0",
+        };
+
+        let in_menubar = self.in_menubar();
+        let opener = XMenuOpenerContext {
+            open: match expanded {
+                true if in_menubar => Some(XMenuOpen::BelowElement(self.root_ref.clone())),
+                true => Some(XMenuOpen::NextToElement(self.root_ref.clone())),
+                false => None,
+            },
+            focus_first: self
+                .menu_ctx
+                .as_ref()
+                .map(|m| m.focus_expanded)
+                .unwrap_or(false),
+            submenu: !in_menubar,
+            on_dismiss: self.on_submenu_dismiss.clone(),
+        };
 
         html! {
           <div
@@ -470,34 +659,30 @@ impl Component for XMenuItem {
             {onpointerdown}
             {onclick}
             {onkeydown}
-            tabindex={if props.disabled {"-1"} else {"1"}}
-            role="menuitem"
+            {tabindex}
+            role={match (&props.group, togglable) {
+                (Some(_), _) => "menuitemradio",
+                (None, true) => "menuitemcheckbox",
+                (None, false) => "menuitem",
+            }}
+            aria-checked={if togglable {Some(if toggled {"true"} else {"false"})} else {None}}
+            aria-haspopup={if has_submenu {Some("menu")} else {None}}
+            aria-expanded={if has_submenu {Some(if expanded {"true"} else {"false"})} else {None}}
             ref={self.root_ref.clone()}
             class={classes.clone()}>
-            <div ref={self.ripples_ref.clone()} class="ripples">
-                {self.ripples.iter().map(|ripple| {
-                    let class = if let XMenuItemRippleClass::Click = ripple.class {
-                       "ripple click-ripple" 
-                    } else {"ripple pointer-down-ripple"};
-
-                    let style = format!(
-                        "width: {}px; height: {}px; top: {}px; left: {}px",
-                        ripple.size as u64,
-                        ripple.size as u64,
-                        ripple.top as u64,
-                        ripple.left as u64);
-
-                    html! {
-                        <div {class} {style}>
-                        </div>
-                    }
-                }).collect::<yew::Html>()}
-            </div>
+            {self.ripples.view("ripples", "ripple")}
             <svg class="checkmark" viewBox="0 0 100 100" preserveAspectRatio="none">
               <path></path>
             </svg>
-            {for props.children.iter()}
-            <svg class="arrow" viewBox="0 0 100 100" hidden={true}>
+            {for props.children.iter().map(|child| match child {
+                XMenuItemChild::Menu(menu) => html! {
+                    <ContextProvider<XMenuOpenerContext> context={opener.clone()}>
+                        {menu}
+                    </ContextProvider<XMenuOpenerContext>>
+                },
+                child => child.into(),
+            })}
+            <svg class="arrow" viewBox="0 0 100 100" hidden={!has_submenu || in_menubar}>
               <path></path>
             </svg>
           </div>
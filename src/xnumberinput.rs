@@ -0,0 +1,727 @@
+//! `XNumberInput` is a field for entering a number. The value is rounded to the `precision` (or
+//! to the decimals of `step`) and kept between `min` and `max`. It is changed by typing, with
+//! the stepper buttons, with the ArrowUp/ArrowDown keys and the mouse wheel while the field is
+//! focused, or by dragging the pointer horizontally over the field while it is not focused.
+//! Holding Shift makes 10 times bigger steps. The stepper buttons are not focusable, so the
+//! ArrowUp/ArrowDown keys are the keyboard path.
+//!
+//! ```
+//! <XNumberInput value={width} min={0.0} max={100.0} step={0.5}
+//!     suffix={String::from("%")} onchange={on_width_change}>
+//!     <XLabel>{"Width"}</XLabel>
+//! </XNumberInput>
+//! ```
+//!
+//! Decimals are displayed and parsed with the separators of the locale of the `Intl` context,
+//! or of the browser when `"feature-intl"` is not enabled. Text using the separators of another
+//! locale (Eg:- `"1.5"` in German) is rejected instead of being guessed. `XLabel` children are
+//! displayed as the placeholder while the field is empty.
+//!
+//! > Enable `"element-x-numberinput"` feature to use this component.
+use std::fmt::Write;
+
+use js_sys::{Array, Function, Object, Reflect};
+use wasm_bindgen::{JsCast, JsValue};
+use web_sys::{
+    Element, FocusEvent, HtmlInputElement, InputEvent, KeyboardEvent, MouseEvent, PointerEvent,
+    WheelEvent,
+};
+use yew::{context::ContextHandle, html, Callback, Children, Component, NodeRef, Properties};
+
+use crate::calculate_computed_size;
+#[cfg(feature = "feature-intl")]
+use crate::intl::Intl;
+use crate::utils::{next_id, EventListener};
+use crate::xcontainer::XContainerContext;
+use crate::xstepper::{XStepper, XStepperAction, XStepperEvent};
+use crate::XComponentSize;
+
+/// Distance (in pixels) which the pointer should move before the value is scrubbed
+const SCRUB_THRESHOLD: f64 = 3.0;
+/// Distance (in pixels) which the pointer should move for each step while scrubbing
+const SCRUB_STEP_DISTANCE: f64 = 8.0;
+/// Steps are multiplied by this while Shift is held
+const SHIFT_MULTIPLIER: f64 = 10.0;
+/// Most fraction digits supported by `Intl.NumberFormat`
+const MAX_PRECISION: u32 = 20;
+
+/// Properties for XNumberInput element
+#[derive(PartialEq, Properties)]
+pub struct XNumberInputProps {
+    /// Value of the field. When given, the owner controls the value and should update it in
+    /// `onchange`. Otherwise the field keeps its own value.
+    #[prop_or_default]
+    pub value: Option<f64>,
+    /// Initial value when `value` is not given. The field starts empty when this is `None`.
+    #[prop_or_default]
+    pub default_value: Option<f64>,
+    #[prop_or_default]
+    pub min: Option<f64>,
+    #[prop_or_default]
+    pub max: Option<f64>,
+    /// Amount which the value is changed with each step
+    #[prop_or(1.0)]
+    pub step: f64,
+    /// Number of digits after the decimal separator (Up to 20). Defaults to the number of
+    /// decimals of `step`.
+    #[prop_or_default]
+    pub precision: Option<u32>,
+    /// Unit displayed after the value (Eg:- `"px"`)
+    #[prop_or_default]
+    pub suffix: Option<String>,
+    /// Whether to display the stepper buttons
+    #[prop_or(true)]
+    pub stepper: bool,
+    /// Displays the stepper inside the field
+    #[prop_or_default]
+    pub condensed: bool,
+    #[prop_or_default]
+    pub disabled: bool,
+    #[prop_or_default]
+    pub size: Option<XComponentSize>,
+    /// When the user changes the value. Receives the new value.
+    #[prop_or_default]
+    pub onchange: Option<Callback<f64>>,
+    /// Classes to apply for root element
+    #[prop_or_default]
+    pub class: Option<String>,
+    /// Styles to apply for root element
+    #[prop_or_default]
+    pub style: Option<String>,
+    /// Placeholder label
+    #[prop_or_default]
+    pub children: Children,
+}
+
+#[doc(hidden)]
+pub enum XNumberInputMessage {
+    ContainerUpdated(XContainerContext),
+    #[cfg(feature = "feature-intl")]
+    IntlUpdated(Intl),
+    Input(InputEvent),
+    Blur(FocusEvent),
+    KeyDown(KeyboardEvent),
+    /// Steps up when `true`. Second value is whether Shift was held.
+    Wheel(bool, bool),
+    Step(XStepperEvent),
+    Click(MouseEvent),
+    PointerDown(PointerEvent),
+    PointerMove(PointerEvent),
+    PointerUp(PointerEvent),
+    PointerCancel,
+}
+
+/// Value being changed by dragging the pointer over the field
+struct Scrub {
+    pointer_x: f64,
+    value: Option<f64>,
+    /// Pointer moved more than the threshold
+    scrubbing: bool,
+}
+
+/// XNumberInput component
+pub struct XNumberInput {
+    input_ref: NodeRef,
+    /// Identifier of the input, which is controlled by the stepper
+    input_id: String,
+    own_value: Option<f64>,
+    /// Text while the user is typing. The formatted value is displayed otherwise.
+    text: Option<String>,
+    locale: Option<String>,
+    format: NumberFormat,
+    scrub: Option<Scrub>,
+    container_ctx: XContainerContext,
+    _container_ctx_listener: ContextHandle<XContainerContext>,
+    #[cfg(feature = "feature-intl")]
+    _intl_listener: Option<ContextHandle<Intl>>,
+    _wheel_listener: Option<EventListener>,
+}
+
+impl XNumberInput {
+    fn value(&self, ctx: &yew::Context<Self>) -> Option<f64> {
+        ctx.props().value.or(self.own_value)
+    }
+
+    fn set_value(&mut self, ctx: &yew::Context<Self>, value: f64) -> bool {
+        let props = ctx.props();
+        let value = normalize(value, precision(props), props.min, props.max);
+        if self.value(ctx) == Some(value) {
+            return false;
+        }
+        if ctx.props().value.is_none() {
+            self.own_value = Some(value);
+        }
+        if let Some(onchange) = &ctx.props().onchange {
+            onchange.emit(value);
+        }
+        true
+    }
+
+    /// Applies the typed text. Text which is not a number is discarded.
+    fn commit(&mut self, ctx: &yew::Context<Self>) -> bool {
+        match self.text.take() {
+            Some(text) => {
+                if let Some(value) = self.format.parse(&text) {
+                    self.set_value(ctx, value);
+                }
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn step(&mut self, ctx: &yew::Context<Self>, increment: bool, shift_key: bool) -> bool {
+        let props = ctx.props();
+        if props.disabled {
+            return false;
+        }
+        // Steps from the typed text. The owner may not have updated a committed value yet.
+        let typed = self.text.take();
+        let edited = typed.is_some();
+        let value = typed
+            .and_then(|text| self.format.parse(&text))
+            .or_else(|| self.value(ctx))
+            .unwrap_or(0.0);
+        let mut step = props.step;
+        if shift_key {
+            step *= SHIFT_MULTIPLIER;
+        }
+        if !increment {
+            step = -step;
+        }
+        self.set_value(ctx, value + step) || edited
+    }
+
+    fn focus_input(&self, select: bool) {
+        if let Some(input) = self.input_ref.cast::<HtmlInputElement>() {
+            input.focus().ok();
+            if select {
+                input.select();
+            }
+        }
+    }
+
+    fn is_focused(&self) -> bool {
+        self.input_ref
+            .cast::<Element>()
+            .map(|input| input.matches(":focus").unwrap_or(false))
+            .unwrap_or(false)
+    }
+}
+
+impl Component for XNumberInput {
+    type Message = XNumberInputMessage;
+    type Properties = XNumberInputProps;
+
+    fn create(ctx: &yew::Context<Self>) -> Self {
+        let (container_ctx, _container_ctx_listener) = ctx
+            .link()
+            .context(ctx.link().callback(XNumberInputMessage::ContainerUpdated))
+            .expect("XContainer should be the root element");
+
+        #[cfg(feature = "feature-intl")]
+        let (locale, _intl_listener) = match ctx
+            .link()
+            .context::<Intl>(ctx.link().callback(XNumberInputMessage::IntlUpdated))
+        {
+            Some((intl, listener)) => (intl.locale(), Some(listener)),
+            None => (None, None),
+        };
+        #[cfg(not(feature = "feature-intl"))]
+        let locale = None;
+
+        XNumberInput {
+            input_ref: NodeRef::default(),
+            input_id: next_id("x-numberinput"),
+            own_value: ctx.props().default_value,
+            text: None,
+            format: NumberFormat::new(locale.as_deref(), precision(ctx.props())),
+            locale,
+            scrub: None,
+            container_ctx,
+            _container_ctx_listener,
+            #[cfg(feature = "feature-intl")]
+            _intl_listener,
+            _wheel_listener: None,
+        }
+    }
+
+    fn update(&mut self, ctx: &yew::Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            XNumberInputMessage::ContainerUpdated(container_ctx) => {
+                self.container_ctx = container_ctx;
+                true
+            }
+            #[cfg(feature = "feature-intl")]
+            XNumberInputMessage::IntlUpdated(intl) => {
+                self.locale = intl.locale();
+                self.format = NumberFormat::new(self.locale.as_deref(), precision(ctx.props()));
+                true
+            }
+            XNumberInputMessage::Input(_e) => {
+                if let Some(input) = self.input_ref.cast::<HtmlInputElement>() {
+                    self.text = Some(input.value());
+                }
+                true
+            }
+            XNumberInputMessage::Blur(_e) => self.commit(ctx),
+            XNumberInputMessage::KeyDown(e) => match e.key().as_str() {
+                "ArrowUp" | "ArrowDown" => {
+                    // Prevent moving the caret
+                    e.prevent_default();
+                    self.step(ctx, e.key() == "ArrowUp", e.shift_key())
+                }
+                "Enter" => self.commit(ctx),
+                "Escape" => self.text.take().is_some(),
+                _ => false,
+            },
+            XNumberInputMessage::Wheel(increment, shift_key) => {
+                self.step(ctx, increment, shift_key)
+            }
+            XNumberInputMessage::Step(event) => {
+                self.step(ctx, event.action == XStepperAction::Increment, event.shift_key)
+            }
+            XNumberInputMessage::Click(e) => {
+                // Clicks of the text are handled on release, and the stepper keeps the focus
+                // where it is
+                let target = e.target().and_then(|t| t.dyn_into::<Element>().ok());
+                let in_stepper = target
+                    .as_ref()
+                    .and_then(|target| target.closest(".x-stepper").ok().flatten())
+                    .is_some();
+                let on_input = target.is_some() && target == self.input_ref.cast::<Element>();
+                if !in_stepper && !on_input && !ctx.props().disabled {
+                    self.focus_input(false);
+                }
+                false
+            }
+            XNumberInputMessage::PointerDown(e) => {
+                // Focused field selects the text as usual
+                if ctx.props().disabled || e.button() != 0 || self.is_focused() {
+                    return false;
+                }
+                // Field is focused on release, unless the value is scrubbed
+                e.prevent_default();
+                if let Some(target) = e.current_target().and_then(|t| t.dyn_into::<Element>().ok())
+                {
+                    target.set_pointer_capture(e.pointer_id()).ok();
+                }
+                self.scrub = Some(Scrub {
+                    pointer_x: e.client_x() as f64,
+                    value: self.value(ctx),
+                    scrubbing: false,
+                });
+                false
+            }
+            XNumberInputMessage::PointerMove(e) => {
+                let (pointer_x, start_value) = match self.scrub.as_mut() {
+                    Some(scrub) => {
+                        let delta = e.client_x() as f64 - scrub.pointer_x;
+                        if !scrub.scrubbing && delta.abs() < SCRUB_THRESHOLD {
+                            return false;
+                        }
+                        scrub.scrubbing = true;
+                        (delta, scrub.value)
+                    }
+                    None => return false,
+                };
+                let mut step = ctx.props().step;
+                if e.shift_key() {
+                    step *= SHIFT_MULTIPLIER;
+                }
+                let steps = (pointer_x / SCRUB_STEP_DISTANCE).trunc();
+                match start_value {
+                    Some(value) => self.set_value(ctx, value + steps * step),
+                    // Empty field gets a value only after the first step
+                    None if steps != 0.0 => self.set_value(ctx, steps * step),
+                    None => false,
+                }
+            }
+            XNumberInputMessage::PointerUp(_e) => {
+                if let Some(scrub) = self.scrub.take() {
+                    if !scrub.scrubbing {
+                        self.focus_input(true);
+                    }
+                }
+                false
+            }
+            XNumberInputMessage::PointerCancel => {
+                self.scrub = None;
+                false
+            }
+        }
+    }
+
+    fn changed(&mut self, ctx: &yew::Context<Self>, old_props: &Self::Properties) -> bool {
+        if precision(ctx.props()) != precision(old_props) {
+            self.format = NumberFormat::new(self.locale.as_deref(), precision(ctx.props()));
+        }
+        true
+    }
+
+    fn rendered(&mut self, ctx: &yew::Context<Self>, first_render: bool) {
+        if !first_render {
+            return;
+        }
+        // Listener of yew is passive, so it can't prevent scrolling the page
+        if let Some(input) = self.input_ref.cast::<HtmlInputElement>() {
+            let link = ctx.link().clone();
+            let target = input.clone();
+            self._wheel_listener = Some(EventListener::new(&target, "wheel", move |e| {
+                let e = match e.dyn_into::<WheelEvent>() {
+                    Ok(e) => e,
+                    Err(_) => return,
+                };
+                if !input.matches(":focus").unwrap_or(false) {
+                    return;
+                }
+                // Shift turns the vertical scrolling into horizontal on some platforms
+                let delta = if e.delta_y() != 0.0 {
+                    e.delta_y()
+                } else {
+                    e.delta_x()
+                };
+                if delta != 0.0 {
+                    e.prevent_default();
+                    link.send_message(XNumberInputMessage::Wheel(delta < 0.0, e.shift_key()));
+                }
+            }));
+        }
+    }
+
+    fn view(&self, ctx: &yew::Context<Self>) -> yew::Html {
+        let props = ctx.props();
+        let value = self.value(ctx);
+        let text = self.text.clone().unwrap_or_else(|| {
+            value
+                .map(|value| self.format.format(value))
+                .unwrap_or_default()
+        });
+        let error = self.text.is_some() && !text.trim().is_empty() && self.format.parse(&text).is_none();
+
+        let mut classes = String::from("x-numberinput");
+
+        if let Some(user_class) = props.class.clone() {
+            classes.push(' ');
+            classes.push_str(user_class.trim());
+        }
+
+        if text.is_empty() {
+            classes.push_str(" empty");
+        }
+
+        if error {
+            classes.push_str(" error");
+        }
+
+        if props.disabled {
+            classes.push_str(" disabled");
+        }
+
+        if props.condensed {
+            classes.push_str(" condensed");
+        }
+
+        if props.stepper {
+            classes.push_str(" hasstepper");
+        }
+
+        if let Some(size) = props.size.clone() {
+            write!(classes, " size-{}", size.to_string()).unwrap();
+        }
+
+        let computed_size =
+            calculate_computed_size(props.size.clone(), self.container_ctx.size.clone());
+        write!(classes, " computedsize-{}", computed_size.to_string()).unwrap();
+
+        let suffix = props.suffix.clone().map(|suffix| {
+            html! {
+                <span class="x-numberinput-suffix">{suffix}</span>
+            }
+        });
+
+        let stepper = props.stepper.then(|| {
+            let at_max = matches!((value, props.max), (Some(value), Some(max)) if value >= max);
+            let at_min = matches!((value, props.min), (Some(value), Some(min)) if value <= min);
+            html! {
+                <XStepper
+                    increment_disabled={props.disabled || at_max}
+                    decrement_disabled={props.disabled || at_min}
+                    controls={self.input_id.clone()}
+                    onstep={ctx.link().callback(XNumberInputMessage::Step)} />
+            }
+        });
+
+        html! {
+            <div
+                style={props.style.clone()}
+                onclick={ctx.link().callback(XNumberInputMessage::Click)}
+                class={classes}>
+                <input
+                    ref={self.input_ref.clone()}
+                    id={self.input_id.clone()}
+                    class="x-numberinput-input"
+                    type="text"
+                    inputmode="decimal"
+                    role="spinbutton"
+                    value={text}
+                    disabled={props.disabled}
+                    aria-valuenow={value.map(|value| value.to_string())}
+                    aria-valuemin={props.min.map(|min| min.to_string())}
+                    aria-valuemax={props.max.map(|max| max.to_string())}
+                    aria-invalid={if error {"true"} else {"false"}}
+                    oninput={ctx.link().callback(XNumberInputMessage::Input)}
+                    onblur={ctx.link().callback(XNumberInputMessage::Blur)}
+                    onkeydown={ctx.link().callback(XNumberInputMessage::KeyDown)}
+                    onpointerdown={ctx.link().callback(XNumberInputMessage::PointerDown)}
+                    onpointermove={ctx.link().callback(XNumberInputMessage::PointerMove)}
+                    onpointerup={ctx.link().callback(XNumberInputMessage::PointerUp)}
+                    onpointercancel={ctx.link().callback(|_| XNumberInputMessage::PointerCancel)} />
+                {for suffix}
+                {for props.children.iter()}
+                {for stepper}
+            </div>
+        }
+    }
+}
+
+/// Number of digits after the decimal separator, up to `MAX_PRECISION`
+fn precision(props: &XNumberInputProps) -> u32 {
+    props
+        .precision
+        .unwrap_or_else(|| step_decimals(props.step))
+        .min(MAX_PRECISION)
+}
+
+/// Number of decimals in the shortest text of the step (Eg:- 2 for `0.25`)
+fn step_decimals(step: f64) -> u32 {
+    if !step.is_finite() {
+        return 0;
+    }
+    step.to_string()
+        .split_once('.')
+        .map_or(0, |(_, fraction)| fraction.len() as u32)
+        .min(MAX_PRECISION)
+}
+
+/// Rounds the value to the precision and keeps it between the limits
+fn normalize(value: f64, precision: u32, min: Option<f64>, max: Option<f64>) -> f64 {
+    let factor = 10f64.powi(precision.min(MAX_PRECISION) as i32);
+    let mut value = (value * factor).round() / factor;
+    if let Some(max) = max {
+        value = value.min(max);
+    }
+    if let Some(min) = min {
+        value = value.max(min);
+    }
+    value
+}
+
+/// Formats and parses the numbers with the decimal separator and the minus sign of a locale.
+/// Digits are always the Latin ones, so they can be typed with any keyboard.
+struct NumberFormat {
+    format: Function,
+    decimal: String,
+    group: String,
+    minus: String,
+}
+
+impl NumberFormat {
+    /// Browser locale is used when `locale` is `None`
+    fn new(locale: Option<&str>, precision: u32) -> Self {
+        let locales = Array::new();
+        if let Some(locale) = locale {
+            locales.push(&JsValue::from_str(locale));
+        }
+
+        let options = Object::new();
+        let precision = JsValue::from(precision.min(MAX_PRECISION));
+        Reflect::set(&options, &"minimumFractionDigits".into(), &precision).ok();
+        Reflect::set(&options, &"maximumFractionDigits".into(), &precision).ok();
+        Reflect::set(&options, &"useGrouping".into(), &JsValue::FALSE).ok();
+        Reflect::set(&options, &"numberingSystem".into(), &"latn".into()).ok();
+        let format = js_sys::Intl::NumberFormat::new(&locales, &options).format();
+
+        // Separators are read from the formatted parts of sample numbers
+        let options = Object::new();
+        Reflect::set(&options, &"numberingSystem".into(), &"latn".into()).ok();
+        let sample = js_sys::Intl::NumberFormat::new(&locales, &options);
+        let part = |number: f64, part_type: &str| {
+            sample
+                .format_to_parts(number)
+                .iter()
+                .find(|part| {
+                    Reflect::get(part, &"type".into())
+                        .ok()
+                        .and_then(|t| t.as_string())
+                        .as_deref()
+                        == Some(part_type)
+                })
+                .and_then(|part| Reflect::get(&part, &"value".into()).ok())
+                .and_then(|value| value.as_string())
+        };
+
+        NumberFormat {
+            format,
+            decimal: part(1.5, "decimal").unwrap_or_else(|| String::from(".")),
+            group: part(1_000_000.0, "group").unwrap_or_default(),
+            minus: part(-1.0, "minusSign").unwrap_or_else(|| String::from("-")),
+        }
+    }
+
+    fn format(&self, value: f64) -> String {
+        self.format
+            .call1(&JsValue::NULL, &JsValue::from_f64(value))
+            .ok()
+            .and_then(|text| text.as_string())
+            .unwrap_or_else(|| value.to_string())
+    }
+
+    /// `None` when the text is not a number
+    fn parse(&self, text: &str) -> Option<f64> {
+        parse(text, &self.decimal, &self.group, &self.minus)
+    }
+}
+
+/// Parses the text written with the given separators and minus sign. ASCII `-` is accepted as
+/// the minus too. Group separators are accepted only in the integer part, between the groups of
+/// 3 digits, so the separators of another locale make the text invalid. `None` when the text is
+/// not a number.
+fn parse(text: &str, decimal: &str, group: &str, minus: &str) -> Option<f64> {
+    let text = text.trim();
+    // Group separator of some locales is a space, which is typed as a regular one
+    let spaced;
+    let text = if !group.is_empty() && group.chars().all(char::is_whitespace) {
+        spaced = text.replace(char::is_whitespace, group);
+        spaced.as_str()
+    } else {
+        text
+    };
+
+    let (negative, text) = match text.strip_prefix(minus).or_else(|| text.strip_prefix('-')) {
+        Some(text) => (true, text),
+        None => (false, text),
+    };
+    let (integer, fraction) = match text.split_once(decimal) {
+        Some((integer, fraction)) => (integer, fraction),
+        None => (text, ""),
+    };
+
+    let is_digits = |part: &str| part.chars().all(|c| c.is_ascii_digit());
+    let groups: Vec<&str> = if group.is_empty() {
+        vec![integer]
+    } else {
+        integer.split(group).collect()
+    };
+    let grouped = groups.len() > 1;
+    let valid_groups = groups.iter().enumerate().all(|(index, part)| {
+        is_digits(part)
+            && match (grouped, index) {
+                (false, _) => true,
+                (true, 0) => (1..=3).contains(&part.len()),
+                (true, _) => part.len() == 3,
+            }
+    });
+    if !valid_groups || !is_digits(fraction) || integer.len() + fraction.len() == 0 {
+        return None;
+    }
+
+    let mut number = String::with_capacity(text.len() + 2);
+    if negative {
+        number.push('-');
+    }
+    number.extend(groups);
+    number.push('.');
+    number.push_str(fraction);
+    number.parse::<f64>().ok().filter(|value| value.is_finite())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{normalize, parse, step_decimals};
+
+    const EN: (&str, &str, &str) = (".", ",", "-");
+    const DE: (&str, &str, &str) = (",", ".", "-");
+    const FR: (&str, &str, &str) = (",", "\u{202f}", "-");
+
+    fn parse_in(text: &str, (decimal, group, minus): (&str, &str, &str)) -> Option<f64> {
+        parse(text, decimal, group, minus)
+    }
+
+    #[test]
+    fn precision_from_step() {
+        assert_eq!(step_decimals(1.0), 0);
+        assert_eq!(step_decimals(10.0), 0);
+        assert_eq!(step_decimals(0.5), 1);
+        assert_eq!(step_decimals(0.25), 2);
+        assert_eq!(step_decimals(0.1), 1);
+    }
+
+    #[test]
+    fn decrement_by_half_step() {
+        let precision = step_decimals(0.5);
+        assert_eq!(normalize(1.0 - 0.5, precision, None, None), 0.5);
+        assert_eq!(normalize(0.5 - 0.5, precision, None, None), 0.0);
+    }
+
+    #[test]
+    fn normalize_rounds_and_clamps() {
+        assert_eq!(normalize(0.1 + 0.2, 1, None, None), 0.3);
+        assert_eq!(normalize(1.26, 1, None, None), 1.3);
+        assert_eq!(normalize(-1.26, 1, None, None), -1.3);
+        assert_eq!(normalize(-0.5, 1, Some(0.0), None), 0.0);
+        assert_eq!(normalize(150.0, 0, Some(0.0), Some(100.0)), 100.0);
+        assert_eq!(normalize(-2.5, 1, Some(-5.0), Some(5.0)), -2.5);
+    }
+
+    #[test]
+    fn parse_en() {
+        assert_eq!(parse_in("1.5", EN), Some(1.5));
+        assert_eq!(parse_in("1,234.5", EN), Some(1234.5));
+        assert_eq!(parse_in("1,234,567", EN), Some(1234567.0));
+        assert_eq!(parse_in(" 42 ", EN), Some(42.0));
+        assert_eq!(parse_in(".5", EN), Some(0.5));
+        assert_eq!(parse_in("1,5", EN), None);
+        assert_eq!(parse_in("1.2.3", EN), None);
+        assert_eq!(parse_in("12,34", EN), None);
+        assert_eq!(parse_in("1.234,5", EN), None);
+    }
+
+    #[test]
+    fn parse_de() {
+        assert_eq!(parse_in("1,5", DE), Some(1.5));
+        assert_eq!(parse_in("1.234,5", DE), Some(1234.5));
+        assert_eq!(parse_in("1.5", DE), None);
+        assert_eq!(parse_in("1,234.5", DE), None);
+        assert_eq!(parse_in("1.234.5", DE), None);
+    }
+
+    #[test]
+    fn parse_fr() {
+        assert_eq!(parse_in("1,5", FR), Some(1.5));
+        assert_eq!(parse_in("1\u{202f}234,5", FR), Some(1234.5));
+        assert_eq!(parse_in("1 234,5", FR), Some(1234.5));
+        assert_eq!(parse_in("1.5", FR), None);
+        assert_eq!(parse_in("12 34", FR), None);
+    }
+
+    #[test]
+    fn parse_minus() {
+        assert_eq!(parse_in("-1.5", EN), Some(-1.5));
+        assert_eq!(parse_in("-1.234,5", DE), Some(-1234.5));
+        assert_eq!(parse("\u{2212}2,5", ",", "\u{a0}", "\u{2212}"), Some(-2.5));
+        assert_eq!(parse("-2,5", ",", "\u{a0}", "\u{2212}"), Some(-2.5));
+        assert_eq!(parse_in("1-", EN), None);
+        assert_eq!(parse_in("--1", EN), None);
+        assert_eq!(parse_in("-", EN), None);
+    }
+
+    #[test]
+    fn parse_rejects_text() {
+        assert_eq!(parse_in("", EN), None);
+        assert_eq!(parse_in("abc", EN), None);
+        assert_eq!(parse_in("1e5", EN), None);
+        assert_eq!(parse_in("+1", EN), None);
+        assert_eq!(parse_in(".", EN), None);
+    }
+}
@@ -0,0 +1,179 @@
+//! `XPopover` is a popup holding arbitrary contents. It is usually placed inside a `XButton`,
+//! which opens it below itself, but it can also be opened with the `open` prop.
+//!
+//! ```
+//! <XButton>
+//!     <XLabel>{"Options"}</XLabel>
+//!     <XPopover>
+//!         <main>{"Contents"}</main>
+//!     </XPopover>
+//! </XButton>
+//! ```
+//!
+//! > Enable `"element-x-popover"` feature to use this component.
+use wasm_bindgen::JsCast;
+use web_sys::{HtmlElement, KeyboardEvent};
+use yew::{html, Callback, Children, Component, ContextHandle, NodeRef, Properties};
+
+use crate::xmenu::{
+    position_popup, position_style, XMenuAlign, XMenuDismiss, XMenuOpen, XMenuOpenerContext,
+    XMenuPosition,
+};
+
+/// Properties for XPopover element
+#[derive(PartialEq, Properties)]
+pub struct XPopoverProps {
+    #[prop_or(XMenuAlign::Start)]
+    pub align: XMenuAlign,
+    /// Opens the popover with the given placement. Popover will be closed when this is `None`.
+    #[prop_or_default]
+    pub open: Option<XMenuOpen>,
+    /// When the popover asks to be closed (Eg:- Escape was pressed). Owner of the `open` prop
+    /// should set it to `None` in reply.
+    #[prop_or_default]
+    pub on_dismiss: Option<Callback<XMenuDismiss>>,
+    #[prop_or_default]
+    pub children: Children,
+}
+
+#[doc(hidden)]
+pub enum XPopoverMessage {
+    OpenerUpdated(XMenuOpenerContext),
+    KeyDown(KeyboardEvent),
+}
+
+/// XPopover component
+pub struct XPopover {
+    node_ref: NodeRef,
+    open: Option<XMenuOpen>,
+    /// Popover is opened, but not positioned yet
+    pending_open: Option<XMenuOpen>,
+    position: Option<XMenuPosition>,
+    opener: Option<XMenuOpenerContext>,
+    _opener_listener: Option<ContextHandle<XMenuOpenerContext>>,
+}
+
+impl XPopover {
+    fn sync_open(&mut self, ctx: &yew::Context<Self>) {
+        let open = ctx
+            .props()
+            .open
+            .clone()
+            .or_else(|| self.opener.as_ref().and_then(|o| o.open.clone()));
+        if open != self.open {
+            self.pending_open = open.clone();
+            self.open = open;
+        }
+    }
+
+    fn dismiss(&self, ctx: &yew::Context<Self>, reason: XMenuDismiss) {
+        if let Some(opener) = &self.opener {
+            opener.on_dismiss.emit(reason);
+        }
+        if let Some(on_dismiss) = ctx.props().on_dismiss.clone() {
+            on_dismiss.emit(reason);
+        }
+    }
+}
+
+impl Component for XPopover {
+    type Message = XPopoverMessage;
+    type Properties = XPopoverProps;
+
+    fn create(ctx: &yew::Context<Self>) -> Self {
+        let (opener, _opener_listener) =
+            match ctx.link().context(ctx.link().callback(XPopoverMessage::OpenerUpdated)) {
+                Some((opener, listener)) => (Some(opener), Some(listener)),
+                None => (None, None),
+            };
+
+        let mut popover = XPopover {
+            node_ref: NodeRef::default(),
+            open: None,
+            pending_open: None,
+            position: None,
+            opener,
+            _opener_listener,
+        };
+        popover.sync_open(ctx);
+        popover
+    }
+
+    fn update(&mut self, ctx: &yew::Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            XPopoverMessage::OpenerUpdated(opener) => {
+                let focus_first = opener.focus_first
+                    && !self.opener.as_ref().map(|o| o.focus_first).unwrap_or(false);
+                self.opener = Some(opener);
+                self.sync_open(ctx);
+
+                // Already opened with the pointer, then asked to focus with the keyboard
+                if focus_first && self.open.is_some() && self.pending_open.is_none() {
+                    if let Some(element) = self.node_ref.cast::<HtmlElement>() {
+                        focus_contents(&element);
+                    }
+                }
+                true
+            }
+            XPopoverMessage::KeyDown(e) => {
+                if e.key() == "Escape" && self.open.is_some() {
+                    e.prevent_default();
+                    e.stop_propagation();
+                    self.dismiss(ctx, XMenuDismiss::Escape);
+                }
+                false
+            }
+        }
+    }
+
+    fn changed(&mut self, ctx: &yew::Context<Self>, _old_props: &Self::Properties) -> bool {
+        self.sync_open(ctx);
+        true
+    }
+
+    fn rendered(&mut self, ctx: &yew::Context<Self>, _first_render: bool) {
+        if let Some(open) = self.pending_open.take() {
+            let element = self.node_ref.cast::<HtmlElement>().unwrap();
+            self.position = Some(position_popup(&element, &open, &ctx.props().align));
+
+            if self.opener.as_ref().map(|o| o.focus_first).unwrap_or(false) {
+                focus_contents(&element);
+            }
+        }
+    }
+
+    fn view(&self, ctx: &yew::Context<Self>) -> yew::Html {
+        let mut classes = String::from("x-popover");
+        if self.open.is_some() {
+            classes.push_str(" opened");
+        }
+
+        let style = match &self.position {
+            Some(position) if self.open.is_some() => position_style(position),
+            _ => String::new(),
+        };
+
+        html! {
+            <div
+                ref={self.node_ref.clone()}
+                role="dialog"
+                tabindex="-1"
+                aria-hidden={if self.open.is_some() {"false"} else {"true"}}
+                onkeydown={ctx.link().callback(XPopoverMessage::KeyDown)}
+                {style}
+                class={classes}>
+                {for ctx.props().children.iter()}
+            </div>
+        }
+    }
+}
+
+/// Focuses the first focusable element of the contents, or the popover itself
+fn focus_contents(element: &HtmlElement) {
+    let first = element
+        .query_selector("[tabindex]:not([tabindex=\"-1\"]), input, textarea, select, button")
+        .ok()
+        .flatten()
+        .and_then(|e| e.dyn_into::<HtmlElement>().ok());
+    first.unwrap_or_else(|| element.clone()).focus().ok();
+}
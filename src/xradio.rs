@@ -0,0 +1,132 @@
+//! `XRadio` is one of the mutually exclusive options of a `XRadios` group.
+//!
+//! ```
+//! <XRadios default_value={String::from("small")}>
+//!     <XRadio value="small"><XLabel>{"Small"}</XLabel></XRadio>
+//!     <XRadio value="medium"><XLabel>{"Medium"}</XLabel></XRadio>
+//!     <XRadio value="large" disabled=true><XLabel>{"Large"}</XLabel></XRadio>
+//! </XRadios>
+//! ```
+//!
+//! > Enable `"element-x-radios"` feature to use this component.
+use std::fmt::Write;
+
+use web_sys::{HtmlElement, KeyboardEvent, MouseEvent};
+use yew::{
+    function_component, html, use_context, use_node_ref, Callback, Children, Properties,
+};
+
+use crate::calculate_computed_size;
+use crate::xcontainer::XContainerContext;
+use crate::XComponentSize;
+
+/// Selection shared with the radios of a group
+#[derive(PartialEq, Clone)]
+pub(crate) struct XRadiosContext {
+    pub(crate) selected: Option<String>,
+    /// Radio which is reachable with Tab. Other radios are focused with the arrow keys.
+    pub(crate) tab_stop: Option<String>,
+    /// All radios are disabled
+    pub(crate) disabled: bool,
+    pub(crate) on_select: Callback<String>,
+}
+
+/// Properties for XRadio element
+#[derive(PartialEq, Properties)]
+pub struct XRadioProps {
+    /// Value of the group when this radio is selected
+    pub value: String,
+    #[prop_or_default]
+    pub disabled: bool,
+    #[prop_or_default]
+    pub size: Option<XComponentSize>,
+    /// Classes to apply for root element
+    #[prop_or_default]
+    pub class: Option<String>,
+    /// Styles to apply for root element
+    #[prop_or_default]
+    pub style: Option<String>,
+    /// Label of the radio
+    #[prop_or_default]
+    pub children: Children,
+}
+
+/// XRadio component
+#[function_component]
+pub fn XRadio(props: &XRadioProps) -> yew::Html {
+    let root_ref = use_node_ref();
+    let group =
+        use_context::<XRadiosContext>().expect("XRadio should be placed inside XRadios");
+    let toggled = group.selected.as_ref() == Some(&props.value);
+    let disabled = props.disabled || group.disabled;
+
+    let mut classes = String::from("x-radio");
+
+    if let Some(user_class) = props.class.clone() {
+        classes.push(' ');
+        classes.push_str(user_class.trim());
+    }
+
+    if toggled {
+        classes.push_str(" toggled");
+    }
+
+    if disabled {
+        classes.push_str(" disabled");
+    }
+
+    if let Some(size) = props.size.clone() {
+        write!(classes, " size-{}", size.to_string()).unwrap();
+    }
+
+    let context =
+        use_context::<XContainerContext>().expect("XContainer should be the root element");
+
+    let computed_size = calculate_computed_size(props.size.clone(), context.size.clone());
+    write!(classes, " computedsize-{}", computed_size.to_string()).unwrap();
+
+    let onclick = {
+        let on_select = group.on_select.clone();
+        let value = props.value.clone();
+        Callback::from(move |_e: MouseEvent| {
+            if !disabled && !toggled {
+                on_select.emit(value.clone());
+            }
+        })
+    };
+
+    // Space selects the focused radio like a click
+    let onkeydown = {
+        let root_ref = root_ref.clone();
+        Callback::from(move |e: KeyboardEvent| {
+            if e.key() == " " && !disabled {
+                // Prevent scrolling the page
+                e.prevent_default();
+                if let Some(root) = root_ref.cast::<HtmlElement>() {
+                    root.click();
+                }
+            }
+        })
+    };
+
+    let tab_stop = !disabled && group.tab_stop.as_ref() == Some(&props.value);
+
+    html! {
+        <div
+            ref={root_ref}
+            role="radio"
+            data-value={props.value.clone()}
+            tabindex={if tab_stop {"0"} else {"-1"}}
+            aria-checked={if toggled {"true"} else {"false"}}
+            aria-disabled={if disabled {"true"} else {"false"}}
+            style={props.style.clone()}
+            {onclick}
+            {onkeydown}
+            class={classes}>
+            <div class="x-radio-indicator">
+                <div class="x-radio-indicator-dot"></div>
+            </div>
+            {for props.children.iter()}
+        </div>
+    }
+}
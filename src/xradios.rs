@@ -0,0 +1,181 @@
+//! `XRadios` is a group of `XRadio`s, which only one of them can be selected. The group is a
+//! single tab stop, and the arrow keys are moving the selection to the next or the previous
+//! radio, skipping the disabled ones.
+//!
+//! ```
+//! <XRadios value={align} onchange={on_align_change}>
+//!     <XRadio value="left"><XLabel>{"Left"}</XLabel></XRadio>
+//!     <XRadio value="center"><XLabel>{"Center"}</XLabel></XRadio>
+//!     <XRadio value="right"><XLabel>{"Right"}</XLabel></XRadio>
+//! </XRadios>
+//! ```
+//!
+//! > Enable `"element-x-radios"` feature to use this component.
+use wasm_bindgen::JsCast;
+use web_sys::{Element, HtmlElement, KeyboardEvent};
+use yew::{
+    function_component, html, use_effect, use_node_ref, use_state, Callback, Children,
+    ContextProvider, NodeRef, Properties,
+};
+
+use crate::xradio::XRadiosContext;
+
+/// Properties for XRadios element
+#[derive(PartialEq, Properties)]
+pub struct XRadiosProps {
+    /// Value of the selected radio. When given, the owner controls the selection and should
+    /// update it in `onchange`. Otherwise the group keeps its own selection.
+    #[prop_or_default]
+    pub value: Option<String>,
+    /// Initial selection when `value` is not given.
+    #[prop_or_default]
+    pub default_value: Option<String>,
+    /// Disables all radios of the group
+    #[prop_or_default]
+    pub disabled: bool,
+    /// Fires with the value of the radio which the user selected
+    #[prop_or_default]
+    pub onchange: Option<Callback<String>>,
+    /// Classes to apply for root element
+    #[prop_or_default]
+    pub class: Option<String>,
+    /// Styles to apply for root element
+    #[prop_or_default]
+    pub style: Option<String>,
+    #[prop_or_default]
+    pub children: Children,
+}
+
+/// XRadios component
+#[function_component]
+pub fn XRadios(props: &XRadiosProps) -> yew::Html {
+    let root_ref = use_node_ref();
+    // Selection of the uncontrolled groups
+    let own_value = use_state(|| props.default_value.clone());
+    let selected = props.value.clone().or_else(|| (*own_value).clone());
+    let tab_stop = use_state(|| None::<String>);
+
+    // Selected radio is the tab stop, or the first enabled one when it is disabled or nothing
+    // is selected. Radios are only known after rendering.
+    {
+        let root_ref = root_ref.clone();
+        let selected = selected.clone();
+        let tab_stop = tab_stop.clone();
+        use_effect(move || {
+            let radios = enabled_radios(&root_ref);
+            let value = radios
+                .iter()
+                .filter_map(|radio| radio.get_attribute("data-value"))
+                .find(|value| Some(value) == selected.as_ref())
+                .or_else(|| {
+                    radios
+                        .first()
+                        .and_then(|radio| radio.get_attribute("data-value"))
+                });
+            if *tab_stop != value {
+                tab_stop.set(value);
+            }
+        });
+    }
+
+    let on_select = {
+        let controlled = props.value.is_some();
+        let onchange = props.onchange.clone();
+        Callback::from(move |value: String| {
+            if !controlled {
+                own_value.set(Some(value.clone()));
+            }
+            if let Some(onchange) = &onchange {
+                onchange.emit(value);
+            }
+        })
+    };
+
+    let onkeydown = {
+        let root_ref = root_ref.clone();
+        Callback::from(move |e: KeyboardEvent| {
+            let forward = match e.key().as_str() {
+                "ArrowDown" | "ArrowRight" => true,
+                "ArrowUp" | "ArrowLeft" => false,
+                _ => return,
+            };
+            let radios = enabled_radios(&root_ref);
+            let current = e
+                .target()
+                .and_then(|target| target.dyn_into::<Element>().ok())
+                .and_then(|target| target.closest(".x-radio").ok().flatten());
+            let current = match current.and_then(|c| radios.iter().position(|r| *r == c)) {
+                Some(current) => current,
+                None => return,
+            };
+            e.prevent_default();
+
+            // Moves around the ends of the group
+            let next = if forward {
+                (current + 1) % radios.len()
+            } else {
+                (current + radios.len() - 1) % radios.len()
+            };
+            if let Some(radio) = radios[next].dyn_ref::<HtmlElement>() {
+                radio.focus().ok();
+                radio.click();
+            }
+        })
+    };
+
+    let context = XRadiosContext {
+        selected,
+        tab_stop: (*tab_stop).clone(),
+        disabled: props.disabled,
+        on_select,
+    };
+
+    let mut classes = String::from("x-radios");
+
+    if let Some(user_class) = props.class.clone() {
+        classes.push(' ');
+        classes.push_str(user_class.trim());
+    }
+
+    if props.disabled {
+        classes.push_str(" disabled");
+    }
+
+    html! {
+        <div
+            ref={root_ref}
+            role="radiogroup"
+            aria-disabled={if props.disabled {"true"} else {"false"}}
+            style={props.style.clone()}
+            {onkeydown}
+            class={classes}>
+            <ContextProvider<XRadiosContext> {context}>
+                {for props.children.iter()}
+            </ContextProvider<XRadiosContext>>
+        </div>
+    }
+}
+
+/// Radios of the group which can be selected, in the document order. Radios of the nested
+/// groups are excluded.
+fn enabled_radios(root_ref: &NodeRef) -> Vec<Element> {
+    let root = match root_ref.cast::<Element>() {
+        Some(root) => root,
+        None => return Vec::new(),
+    };
+    let radios = match root.query_selector_all(".x-radio:not(.disabled)") {
+        Ok(radios) => radios,
+        Err(_) => return Vec::new(),
+    };
+    (0..radios.length())
+        .filter_map(|i| radios.item(i))
+        .filter_map(|radio| radio.dyn_into::<Element>().ok())
+        .filter(|radio| {
+            radio
+                .parent_element()
+                .and_then(|parent| parent.closest(".x-radios").ok().flatten())
+                .map(|group| group == root)
+                .unwrap_or(false)
+        })
+        .collect()
+}
@@ -0,0 +1,387 @@
+//! `XShortcut` displays a keyboard shortcut using the conventions of the current platform
+//! (Eg:- `⌘⇧S` on macOS and `Ctrl+Shift+S` on other platforms). Place it inside a `XMenuItem`
+//! to show the shortcut on the right side of the item.
+//!
+//! ```
+//! <XMenuItem>
+//!     <XLabel>{"Save As"}</XLabel>
+//!     <XShortcut value={XKeyCombination::new("S").control().shift()}/>
+//! </XMenuItem>
+//! ```
+//!
+//! Key combinations can also be registered to the shortcut registry of `XContainer` to invoke a
+//! callback whenever the combination is pressed inside the container. `XMenuItem` does it for
+//! its `shortcut` prop, so the item is clicked even when its menu is closed.
+//!
+//! ```
+//! let (container_ctx, _) = ctx.link().context::<XContainerContext>(Callback::noop()).unwrap();
+//! let registration = container_ctx
+//!     .shortcuts
+//!     .register(XKeyCombination::new("F5"), ctx.link().callback(Message::Refresh));
+//! // Keep the registration in the component state. Shortcut is removed when it is dropped.
+//! ```
+//!
+//! > Enable `"element-x-shortcut"` feature to use this component.
+use std::cell::RefCell;
+use std::fmt;
+use std::rc::{Rc, Weak};
+
+use web_sys::KeyboardEvent;
+use yew::{html, Callback, Component, Properties};
+
+/// A key with the modifiers which should be held while pressing it.
+#[derive(PartialEq, Eq, Hash, Clone, Debug)]
+pub struct XKeyCombination {
+    /// Value of `KeyboardEvent.key` (Eg:- `"S"`, `"Enter"`, `"ArrowUp"`). Letters are compared
+    /// case insensitively.
+    pub key: String,
+    pub control: bool,
+    pub alt: bool,
+    pub shift: bool,
+    /// Command key on macOS, Windows key on other platforms
+    pub meta: bool,
+}
+
+impl XKeyCombination {
+    pub fn new(key: &str) -> Self {
+        Self {
+            key: String::from(key),
+            control: false,
+            alt: false,
+            shift: false,
+            meta: false,
+        }
+    }
+
+    pub fn control(mut self) -> Self {
+        self.control = true;
+        self
+    }
+
+    pub fn alt(mut self) -> Self {
+        self.alt = true;
+        self
+    }
+
+    pub fn shift(mut self) -> Self {
+        self.shift = true;
+        self
+    }
+
+    pub fn meta(mut self) -> Self {
+        self.meta = true;
+        self
+    }
+
+    /// Command key on macOS and Control key on other platforms
+    pub fn primary(self) -> Self {
+        if is_mac() {
+            self.meta()
+        } else {
+            self.control()
+        }
+    }
+
+    /// Whether both are the same combination, ignoring the case of the key
+    fn same_as(&self, other: &XKeyCombination) -> bool {
+        self.key.eq_ignore_ascii_case(&other.key)
+            && self.control == other.control
+            && self.alt == other.alt
+            && self.shift == other.shift
+            && self.meta == other.meta
+    }
+
+    /// Whether the given key press is this combination
+    pub fn matches(&self, e: &KeyboardEvent) -> bool {
+        if e.ctrl_key() != self.control
+            || e.alt_key() != self.alt
+            || e.shift_key() != self.shift
+            || e.meta_key() != self.meta
+        {
+            return false;
+        }
+
+        if e.key().eq_ignore_ascii_case(&self.key) {
+            return true;
+        }
+
+        // Alt and Shift are changing the key on some layouts, so compare the physical key too
+        let code = e.code();
+        code.strip_prefix("Key")
+            .or_else(|| code.strip_prefix("Digit"))
+            .map(|key| key.eq_ignore_ascii_case(&self.key))
+            .unwrap_or(false)
+    }
+
+    /// Text of the shortcut in the platform conventions
+    pub fn label(&self) -> String {
+        self.label_for(is_mac())
+    }
+
+    /// Text of the shortcut in the conventions of macOS when `mac` is `true`, or of the other
+    /// platforms
+    fn label_for(&self, mac: bool) -> String {
+        let mut parts: Vec<&str> = Vec::new();
+
+        if mac {
+            // Order used by the macOS menus
+            if self.control {
+                parts.push("⌃");
+            }
+            if self.alt {
+                parts.push("⌥");
+            }
+            if self.shift {
+                parts.push("⇧");
+            }
+            if self.meta {
+                parts.push("⌘");
+            }
+        } else {
+            if self.control {
+                parts.push("Ctrl");
+            }
+            if self.alt {
+                parts.push("Alt");
+            }
+            if self.shift {
+                parts.push("Shift");
+            }
+            if self.meta {
+                parts.push("Meta");
+            }
+        }
+
+        let key = key_label(&self.key, mac);
+        parts.push(&key);
+        parts.join(if mac { "" } else { "+" })
+    }
+}
+
+fn key_label(key: &str, mac: bool) -> String {
+    let label = match key {
+        " " | "Space" => "Space",
+        "ArrowUp" => if mac { "↑" } else { "Up" },
+        "ArrowDown" => if mac { "↓" } else { "Down" },
+        "ArrowLeft" => if mac { "←" } else { "Left" },
+        "ArrowRight" => if mac { "→" } else { "Right" },
+        "Enter" => if mac { "↩" } else { "Enter" },
+        "Backspace" => if mac { "⌫" } else { "Backspace" },
+        "Delete" => if mac { "⌦" } else { "Del" },
+        "Escape" => if mac { "⎋" } else { "Esc" },
+        "Tab" => if mac { "⇥" } else { "Tab" },
+        "PageUp" => if mac { "⇞" } else { "PgUp" },
+        "PageDown" => if mac { "⇟" } else { "PgDn" },
+        "Home" => if mac { "↖" } else { "Home" },
+        "End" => if mac { "↘" } else { "End" },
+        _ => return key.to_uppercase(),
+    };
+    String::from(label)
+}
+
+impl fmt::Display for XKeyCombination {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.label())
+    }
+}
+
+/// Error returned when a key combination is already registered
+#[derive(PartialEq, Clone, Debug)]
+pub struct XShortcutConflict(pub XKeyCombination);
+
+impl fmt::Display for XShortcutConflict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Shortcut {} is already registered", self.0)
+    }
+}
+
+impl std::error::Error for XShortcutConflict {}
+
+#[derive(Default)]
+struct XShortcutRegistryInner {
+    next_id: u32,
+    shortcuts: Vec<(u32, XKeyCombination, Callback<KeyboardEvent>)>,
+}
+
+/// Shortcuts registered to a `XContainer`. Available as `XContainerContext::shortcuts`.
+#[derive(Clone, Default)]
+pub struct XShortcutRegistry(Rc<RefCell<XShortcutRegistryInner>>);
+
+impl PartialEq for XShortcutRegistry {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl XShortcutRegistry {
+    /// Invokes the callback whenever the combination is pressed inside the container. Fails when
+    /// the combination is already registered by another component, so the first registration
+    /// wins until it is dropped.
+    pub fn register(
+        &self,
+        combination: XKeyCombination,
+        callback: Callback<KeyboardEvent>,
+    ) -> Result<XShortcutRegistration, XShortcutConflict> {
+        let mut inner = self.0.borrow_mut();
+        if inner.shortcuts.iter().any(|(_, c, _)| c.same_as(&combination)) {
+            return Err(XShortcutConflict(combination));
+        }
+
+        let id = inner.next_id;
+        inner.next_id += 1;
+        inner.shortcuts.push((id, combination, callback));
+
+        Ok(XShortcutRegistration {
+            registry: Rc::downgrade(&self.0),
+            id,
+        })
+    }
+
+    /// Invokes the callback of the pressed combination. Returns `false` when nothing matched.
+    pub(crate) fn dispatch(&self, e: &KeyboardEvent) -> bool {
+        // Callback may register other shortcuts, so don't keep the registry borrowed
+        let callback = self
+            .0
+            .borrow()
+            .shortcuts
+            .iter()
+            .find(|(_, combination, _)| combination.matches(e))
+            .map(|(_, _, callback)| callback.clone());
+
+        match callback {
+            Some(callback) => {
+                callback.emit(e.clone());
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+/// Registered shortcut. Shortcut is unregistered when this is dropped.
+pub struct XShortcutRegistration {
+    registry: Weak<RefCell<XShortcutRegistryInner>>,
+    id: u32,
+}
+
+impl Drop for XShortcutRegistration {
+    fn drop(&mut self) {
+        if let Some(registry) = self.registry.upgrade() {
+            registry
+                .borrow_mut()
+                .shortcuts
+                .retain(|(id, _, _)| *id != self.id);
+        }
+    }
+}
+
+/// Whether the app is running on an Apple platform
+pub(crate) fn is_mac() -> bool {
+    web_sys::window()
+        .and_then(|window| window.navigator().platform().ok())
+        .map(|platform| platform.starts_with("Mac") || platform.starts_with("iP"))
+        .unwrap_or(false)
+}
+
+/// Properties for XShortcut element
+#[derive(PartialEq, Properties)]
+pub struct XShortcutProps {
+    /// Key combination to display
+    pub value: XKeyCombination,
+    /// Whether that shortcut is disabled or not
+    #[prop_or_default]
+    pub disabled: bool,
+}
+
+/// XShortcut component
+pub struct XShortcut;
+
+impl Component for XShortcut {
+    type Message = ();
+
+    type Properties = XShortcutProps;
+
+    fn create(_ctx: &yew::Context<Self>) -> Self {
+        XShortcut
+    }
+
+    fn view(&self, ctx: &yew::Context<Self>) -> yew::Html {
+        let props = ctx.props();
+        let mut classes = String::from("x-shortcut");
+
+        if props.disabled {
+            classes.push_str(" disabled");
+        }
+
+        html! {
+            <div class={classes}>{props.value.label()}</div>
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{XKeyCombination, XShortcutRegistry};
+    use yew::Callback;
+
+    #[test]
+    fn label_on_mac() {
+        let save = XKeyCombination::new("s").meta().shift();
+        assert_eq!(save.label_for(true), "⇧⌘S");
+
+        let all = XKeyCombination::new("ArrowUp").meta().shift().alt().control();
+        assert_eq!(all.label_for(true), "⌃⌥⇧⌘↑");
+    }
+
+    #[test]
+    fn label_on_other_platforms() {
+        let save = XKeyCombination::new("s").control().shift();
+        assert_eq!(save.label_for(false), "Ctrl+Shift+S");
+
+        let all = XKeyCombination::new("ArrowUp").meta().shift().alt().control();
+        assert_eq!(all.label_for(false), "Ctrl+Alt+Shift+Meta+Up");
+
+        assert_eq!(XKeyCombination::new("F5").label_for(false), "F5");
+        assert_eq!(XKeyCombination::new(" ").alt().label_for(false), "Alt+Space");
+    }
+
+    #[test]
+    fn same_combination_in_any_modifier_order() {
+        let a = XKeyCombination::new("S").control().shift();
+        let b = XKeyCombination::new("s").shift().control();
+        assert!(a.same_as(&b));
+        assert!(b.same_as(&a));
+    }
+
+    #[test]
+    fn different_modifiers_are_different_combinations() {
+        let a = XKeyCombination::new("S").control();
+        assert!(!a.same_as(&XKeyCombination::new("S").control().shift()));
+        assert!(!a.same_as(&XKeyCombination::new("S").meta()));
+        assert!(!a.same_as(&XKeyCombination::new("D").control()));
+    }
+
+    #[test]
+    fn registering_same_combination_conflicts() {
+        let registry = XShortcutRegistry::default();
+        let first = registry.register(
+            XKeyCombination::new("S").control().shift(),
+            Callback::from(|_| ()),
+        );
+        assert!(first.is_ok());
+
+        let second = registry.register(
+            XKeyCombination::new("s").shift().control(),
+            Callback::from(|_| ()),
+        );
+        assert!(second.is_err());
+
+        // Combination is free again after the registration is dropped
+        drop(first);
+        let third = registry.register(
+            XKeyCombination::new("s").shift().control(),
+            Callback::from(|_| ()),
+        );
+        assert!(third.is_ok());
+    }
+}
@@ -0,0 +1,214 @@
+//! `XStepper` is a pair of increment and decrement buttons. Holding a button down repeats the
+//! step until it is released.
+//!
+//! Buttons are not focusable, so the focus stays in the field they are stepping. That field
+//! should handle ArrowUp/ArrowDown as the keyboard path (Like `XNumberInput` does), and its id is
+//! given in `controls`.
+//!
+//! ```
+//! <XStepper onstep={Callback::from(|e: XStepperEvent| log(e.action))} />
+//! ```
+//!
+//! > Enable `"element-x-stepper"` feature to use this component.
+use std::fmt::{self, Write};
+use std::time::Duration;
+
+use prokio::time::sleep;
+use wasm_bindgen::JsCast;
+use web_sys::{Element, PointerEvent};
+use yew::{html, Callback, Component, Properties};
+
+/// Time (in milliseconds) to hold a button before the step is repeated
+const REPEAT_DELAY: u64 = 500;
+/// Time (in milliseconds) between the repeated steps
+const REPEAT_INTERVAL: u64 = 50;
+
+/// Button of the XStepper component
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum XStepperAction {
+    Increment,
+    Decrement,
+}
+
+impl fmt::Display for XStepperAction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            XStepperAction::Increment => "increment",
+            XStepperAction::Decrement => "decrement",
+        })
+    }
+}
+
+/// A step made with the XStepper component
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub struct XStepperEvent {
+    pub action: XStepperAction,
+    /// Shift was held when the button was pressed. Usually makes a bigger step.
+    pub shift_key: bool,
+}
+
+/// Properties for XStepper element
+#[derive(PartialEq, Properties)]
+pub struct XStepperProps {
+    #[prop_or_default]
+    pub increment_disabled: bool,
+    #[prop_or_default]
+    pub decrement_disabled: bool,
+    /// Identifier of the element whose value is stepped
+    #[prop_or_default]
+    pub controls: Option<String>,
+    /// When a button is pressed, and repeatedly while it is held
+    #[prop_or_default]
+    pub onstep: Option<Callback<XStepperEvent>>,
+    /// Classes to apply for root element
+    #[prop_or_default]
+    pub class: Option<String>,
+    /// Styles to apply for root element
+    #[prop_or_default]
+    pub style: Option<String>,
+}
+
+#[doc(hidden)]
+pub enum XStepperMessage {
+    Press(XStepperAction, PointerEvent),
+    Release,
+    /// Repeats the step of the press with the given identifier
+    Repeat(u32),
+}
+
+/// XStepper component
+pub struct XStepper {
+    /// Button held down, and whether Shift was held when it was pressed
+    pressed: Option<XStepperEvent>,
+    /// Identifier of the last press. Repeats of the previous presses are ignored.
+    press_id: u32,
+}
+
+impl XStepper {
+    fn is_disabled(ctx: &yew::Context<Self>, action: XStepperAction) -> bool {
+        match action {
+            XStepperAction::Increment => ctx.props().increment_disabled,
+            XStepperAction::Decrement => ctx.props().decrement_disabled,
+        }
+    }
+
+    /// Emits the step, then schedules the next one
+    fn step(&self, ctx: &yew::Context<Self>, event: XStepperEvent, delay: u64) {
+        if let Some(onstep) = &ctx.props().onstep {
+            onstep.emit(event);
+        }
+        let id = self.press_id;
+        ctx.link().send_future(async move {
+            sleep(Duration::from_millis(delay)).await;
+            XStepperMessage::Repeat(id)
+        });
+    }
+}
+
+impl Component for XStepper {
+    type Message = XStepperMessage;
+    type Properties = XStepperProps;
+
+    fn create(_ctx: &yew::Context<Self>) -> Self {
+        XStepper {
+            pressed: None,
+            press_id: 0,
+        }
+    }
+
+    fn update(&mut self, ctx: &yew::Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            XStepperMessage::Press(action, e) => {
+                // Keep the focus where it is (Eg:- in the number input)
+                e.prevent_default();
+                if e.button() != 0 || Self::is_disabled(ctx, action) {
+                    return false;
+                }
+                // Keep receiving the pointer events, so the button is released outside itself
+                if let Some(target) = e.current_target().and_then(|t| t.dyn_into::<Element>().ok())
+                {
+                    target.set_pointer_capture(e.pointer_id()).ok();
+                }
+                let event = XStepperEvent {
+                    action,
+                    shift_key: e.shift_key(),
+                };
+                self.press_id += 1;
+                self.pressed = Some(event);
+                self.step(ctx, event, REPEAT_DELAY);
+                true
+            }
+            XStepperMessage::Release => {
+                self.press_id += 1;
+                self.pressed.take().is_some()
+            }
+            XStepperMessage::Repeat(id) => {
+                match self.pressed {
+                    Some(event) if id == self.press_id => {
+                        // Stop at the limit
+                        if Self::is_disabled(ctx, event.action) {
+                            self.pressed = None;
+                            return true;
+                        }
+                        self.step(ctx, event, REPEAT_INTERVAL);
+                    }
+                    _ => {}
+                }
+                false
+            }
+        }
+    }
+
+    fn view(&self, ctx: &yew::Context<Self>) -> yew::Html {
+        let props = ctx.props();
+        let mut classes = String::from("x-stepper");
+
+        if let Some(user_class) = props.class.clone() {
+            classes.push(' ');
+            classes.push_str(user_class.trim());
+        }
+
+        if let Some(pressed) = &self.pressed {
+            write!(classes, " pressed-{}", pressed.action).unwrap();
+        }
+
+        let button = |action: XStepperAction| {
+            let disabled = Self::is_disabled(ctx, action);
+            let mut class = format!("x-stepper-{}-button", action);
+            if disabled {
+                class.push_str(" disabled");
+            }
+            let label = match action {
+                XStepperAction::Increment => "Increment",
+                XStepperAction::Decrement => "Decrement",
+            };
+            html! {
+                <div
+                    {class}
+                    role="button"
+                    tabindex="-1"
+                    aria-label={label}
+                    aria-controls={props.controls.clone()}
+                    aria-disabled={if disabled {"true"} else {"false"}}
+                    onpointerdown={ctx.link().callback(move |e| XStepperMessage::Press(action, e))}
+                    onpointerup={ctx.link().callback(|_| XStepperMessage::Release)}
+                    onpointercancel={ctx.link().callback(|_| XStepperMessage::Release)}>
+                    <svg
+                        class={format!("x-stepper-{}-arrow", action)}
+                        viewBox="0 0 100 100"
+                        preserveAspectRatio="none">
+                        <path class="x-stepper-arrow-path"></path>
+                    </svg>
+                </div>
+            }
+        };
+
+        html! {
+            <div class={classes} style={props.style.clone()}>
+                {button(XStepperAction::Decrement)}
+                <div class="x-stepper-separator"></div>
+                {button(XStepperAction::Increment)}
+            </div>
+        }
+    }
+}
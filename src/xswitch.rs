@@ -0,0 +1,312 @@
+//! `XSwitch` toggles an option on and off. It is toggled by clicking or by dragging its thumb,
+//! which toggles when the thumb is released past the middle of the track.
+//!
+//! ```
+//! <XSwitch toggled={wifi} ontoggle={on_wifi_toggle}>
+//!     <XLabel>{"Wi-Fi"}</XLabel>
+//! </XSwitch>
+//! ```
+//!
+//! > Enable `"element-x-switch"` feature to use this component.
+use std::fmt::Write;
+
+use wasm_bindgen::JsCast;
+use web_sys::{Element, HtmlElement, KeyboardEvent, MouseEvent, PointerEvent};
+use yew::{
+    function_component, html, use_context, use_effect, use_force_update, use_mut_ref,
+    use_node_ref, use_state, Callback, Children, Properties,
+};
+
+use crate::calculate_computed_size;
+use crate::utils::{new_animation, property_keyframes};
+use crate::xcontainer::XContainerContext;
+use crate::{CSSEasing, Transition, XComponentSize};
+
+/// Distance (in pixels) which the pointer should move before the thumb is dragged
+const DRAG_THRESHOLD: f64 = 3.0;
+
+/// Properties for XSwitch element
+#[derive(PartialEq, Properties)]
+pub struct XSwitchProps {
+    /// Whether this switch is toggled. When given, the owner controls the state and should
+    /// update it in `ontoggle`. Otherwise the switch keeps its own state.
+    #[prop_or_default]
+    pub toggled: Option<bool>,
+    /// Initial toggled state when `toggled` is not given.
+    #[prop_or_default]
+    pub default_toggled: bool,
+    #[prop_or_default]
+    pub disabled: bool,
+    #[prop_or_default]
+    pub size: Option<XComponentSize>,
+    /// When the user toggles the switch. Receives the new toggled state.
+    #[prop_or_default]
+    pub ontoggle: Option<Callback<(MouseEvent, bool)>>,
+    /// Animation of the thumb moving to the other side
+    #[prop_or(Transition::new("left", 150.0, CSSEasing::CubicBezier(0.4, 0.0, 0.2, 1.0)))]
+    pub thumb_transition: Transition,
+    /// Classes to apply for root element
+    #[prop_or_default]
+    pub class: Option<String>,
+    /// Styles to apply for root element
+    #[prop_or_default]
+    pub style: Option<String>,
+    /// Label of the switch
+    #[prop_or_default]
+    pub children: Children,
+}
+
+/// Thumb slide to run after rendering
+struct Slide {
+    /// Position of the thumb before toggling
+    from: f64,
+    /// Toggled state before clicking. Slides only when the state is changed, since the owner of
+    /// a controlled switch may keep it. Thumb always slides back after dragging.
+    toggled: Option<bool>,
+}
+
+/// Thumb being moved with the pointer
+struct Drag {
+    pointer_x: f64,
+    thumb_left: f64,
+    /// Pointer moved more than the threshold
+    dragging: bool,
+}
+
+/// XSwitch component
+#[function_component]
+pub fn XSwitch(props: &XSwitchProps) -> yew::Html {
+    let root_ref = use_node_ref();
+    let thumb_ref = use_node_ref();
+    let own_toggled = use_state(|| props.default_toggled);
+    let toggled = props.toggled.unwrap_or(*own_toggled);
+    let force_update = use_force_update();
+
+    let drag = use_mut_ref(|| None::<Drag>);
+    // Click following a drag is already handled on release
+    let suppress_click = use_mut_ref(|| false);
+    let slide = use_mut_ref(|| None::<Slide>);
+
+    let mut classes = String::from("x-switch");
+
+    if let Some(user_class) = props.class.clone() {
+        classes.push(' ');
+        classes.push_str(user_class.trim());
+    }
+
+    if toggled {
+        classes.push_str(" toggled");
+    }
+
+    if props.disabled {
+        classes.push_str(" disabled");
+    }
+
+    if let Some(size) = props.size.clone() {
+        write!(classes, " size-{}", size.to_string()).unwrap();
+    }
+
+    let context =
+        use_context::<XContainerContext>().expect("XContainer should be the root element");
+
+    let computed_size = calculate_computed_size(props.size.clone(), context.size.clone());
+    write!(classes, " computedsize-{}", computed_size.to_string()).unwrap();
+
+    {
+        let thumb_ref = thumb_ref.clone();
+        let slide = slide.clone();
+        let transition = props.thumb_transition.clone();
+        use_effect(move || {
+            let from = slide
+                .borrow_mut()
+                .take()
+                .filter(|slide| slide.toggled != Some(toggled))
+                .map(|slide| slide.from);
+            if let (Some(from), Some(thumb)) = (from, thumb_ref.cast::<HtmlElement>()) {
+                let to = thumb.offset_left() as f64;
+                if from != to {
+                    new_animation(
+                        &thumb,
+                        &property_keyframes(
+                            &transition.property,
+                            &[&format!("{}px", from), &format!("{}px", to)],
+                        ),
+                        transition.duration,
+                        &transition.easing,
+                    );
+                }
+            }
+        });
+    }
+
+    let toggle = {
+        let ontoggle = props.ontoggle.clone();
+        let controlled = props.toggled.is_some();
+        let own_toggled = own_toggled.clone();
+        move |e: MouseEvent, new_toggled: bool| {
+            if !controlled {
+                own_toggled.set(new_toggled);
+            }
+            if let Some(ontoggle) = &ontoggle {
+                ontoggle.emit((e, new_toggled));
+            }
+        }
+    };
+
+    let onclick = {
+        let toggle = toggle.clone();
+        let thumb_ref = thumb_ref.clone();
+        let slide = slide.clone();
+        let suppress_click = suppress_click.clone();
+        let disabled = props.disabled;
+        Callback::from(move |e: MouseEvent| {
+            if std::mem::take(&mut *suppress_click.borrow_mut()) || disabled {
+                return;
+            }
+            if let Some(thumb) = thumb_ref.cast::<HtmlElement>() {
+                *slide.borrow_mut() = Some(Slide {
+                    from: thumb.offset_left() as f64,
+                    toggled: Some(toggled),
+                });
+            }
+            toggle(e, !toggled);
+        })
+    };
+
+    let onpointerdown = {
+        let drag = drag.clone();
+        let thumb_ref = thumb_ref.clone();
+        let suppress_click = suppress_click.clone();
+        let disabled = props.disabled;
+        Callback::from(move |e: PointerEvent| {
+            *suppress_click.borrow_mut() = false;
+            if disabled || e.button() != 0 {
+                return;
+            }
+            let thumb = match thumb_ref.cast::<HtmlElement>() {
+                Some(thumb) => thumb,
+                None => return,
+            };
+            // Keep receiving the pointer events while dragging outside the switch
+            if let Some(target) = e.current_target().and_then(|t| t.dyn_into::<Element>().ok()) {
+                target.set_pointer_capture(e.pointer_id()).ok();
+            }
+            *drag.borrow_mut() = Some(Drag {
+                pointer_x: e.client_x() as f64,
+                thumb_left: thumb.offset_left() as f64,
+                dragging: false,
+            });
+        })
+    };
+
+    let onpointermove = {
+        let drag = drag.clone();
+        let thumb_ref = thumb_ref.clone();
+        Callback::from(move |e: PointerEvent| {
+            let mut drag = drag.borrow_mut();
+            let (drag, thumb) = match (drag.as_mut(), thumb_ref.cast::<HtmlElement>()) {
+                (Some(drag), Some(thumb)) => (drag, thumb),
+                _ => return,
+            };
+            let delta = e.client_x() as f64 - drag.pointer_x;
+            if !drag.dragging && delta.abs() < DRAG_THRESHOLD {
+                return;
+            }
+            drag.dragging = true;
+
+            let max_left = thumb
+                .offset_parent()
+                .map(|track| track.client_width() - thumb.offset_width())
+                .unwrap_or(0) as f64;
+            let left = (drag.thumb_left + delta).min(max_left).max(0.0);
+            thumb.style().set_property("left", &format!("{}px", left)).ok();
+        })
+    };
+
+    let onpointerup = {
+        let drag = drag.clone();
+        let thumb_ref = thumb_ref.clone();
+        let slide = slide.clone();
+        let suppress_click = suppress_click.clone();
+        let force_update = force_update.clone();
+        let toggle = toggle.clone();
+        Callback::from(move |e: PointerEvent| {
+            let dragging = drag.borrow_mut().take().map(|drag| drag.dragging);
+            let thumb = match thumb_ref.cast::<HtmlElement>() {
+                Some(thumb) if dragging == Some(true) => thumb,
+                _ => return,
+            };
+            *suppress_click.borrow_mut() = true;
+
+            // Thumb is toggled when its center passes the center of the track
+            let left = thumb.offset_left() as f64;
+            let track_width = thumb
+                .offset_parent()
+                .map(|track| track.client_width())
+                .unwrap_or(0) as f64;
+            let new_toggled = left + thumb.offset_width() as f64 / 2.0 > track_width / 2.0;
+
+            thumb.style().remove_property("left").ok();
+            *slide.borrow_mut() = Some(Slide {
+                from: left,
+                toggled: None,
+            });
+            if new_toggled != toggled {
+                toggle(MouseEvent::clone(&e), new_toggled);
+            }
+            // Thumb slides back even when nothing is changed
+            force_update.force_update();
+        })
+    };
+
+    let onpointercancel = {
+        let drag = drag.clone();
+        let thumb_ref = thumb_ref.clone();
+        Callback::from(move |_e: PointerEvent| {
+            drag.borrow_mut().take();
+            if let Some(thumb) = thumb_ref.cast::<HtmlElement>() {
+                thumb.style().remove_property("left").ok();
+            }
+        })
+    };
+
+    // Space toggles the switch like a click
+    let onkeydown = {
+        let root_ref = root_ref.clone();
+        let disabled = props.disabled;
+        Callback::from(move |e: KeyboardEvent| {
+            if e.key() == " " && !disabled {
+                // Prevent scrolling the page
+                e.prevent_default();
+                if !e.repeat() {
+                    if let Some(root) = root_ref.cast::<HtmlElement>() {
+                        root.click();
+                    }
+                }
+            }
+        })
+    };
+
+    html! {
+        <div
+            ref={root_ref}
+            role="switch"
+            tabindex={if props.disabled {"-1"} else {"0"}}
+            aria-checked={if toggled {"true"} else {"false"}}
+            aria-disabled={if props.disabled {"true"} else {"false"}}
+            style={props.style.clone()}
+            {onclick}
+            {onkeydown}
+            {onpointerdown}
+            {onpointermove}
+            {onpointerup}
+            {onpointercancel}
+            class={classes}>
+            <div class="x-switch-indicator">
+                <div class="x-switch-indicator-track"></div>
+                <div ref={thumb_ref} class="x-switch-indicator-thumb"></div>
+            </div>
+            {for props.children.iter()}
+        </div>
+    }
+}
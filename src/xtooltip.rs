@@ -1,47 +1,109 @@
-//! `XTooltip` component will allow you to use tooltips on buttons. We are recommending to use the
-//! `XTooltip` with a `XButton` element. Otherwise you have to define the css positin by own.
+//! `XTooltip` displays a hint next to an element when it is hovered or focused. By default the
+//! tooltip is attached to its parent element, so it can be placed inside a `XButton` or any
+//! other element.
 //!
 //! Simple:-
 //! ```
 //! <XButton>
-//!     <XLabel>My Button</XLabel>
+//!     <XLabel>{"My Button"}</XLabel>
 //!     <XTooltip>
-//!         <XLabel>{"My Tooltip"}</XTooltip>
+//!         <XLabel>{"My Tooltip"}</XLabel>
 //!     </XTooltip>
 //! </XButton>
-//!
-//! Alignment:-
 //! ```
 //!
 //! Alignment:-
 //! ```
 //! <XButton>
-//!     <XLabel>My Button</XLabel>
+//!     <XLabel>{"My Button"}</XLabel>
 //!     <XTooltip align={XTooltipAlign::Right}>
-//!         <XLabel>{"My Tooltip"}</XTooltip>
+//!         <XLabel>{"My Tooltip"}</XLabel>
 //!     </XTooltip>
 //! </XButton>
 //! ```
 //!
+//! Any element:-
+//! ```
+//! let anchor = use_node_ref();
+//! html! {
+//!     <>
+//!         <input ref={anchor.clone()} />
+//!         <XTooltip anchor={anchor}>
+//!             <XLabel>{"My Tooltip"}</XLabel>
+//!         </XTooltip>
+//!     </>
+//! }
+//! ```
+//!
+//! Error of a form control:-
+//! ```
+//! html! {
+//!     <>
+//!         <input ref={anchor.clone()} required=true />
+//!         <XTooltip anchor={anchor} r#type={XTooltipType::Error} open={invalid}>
+//!             <XLabel>{"This field is required"}</XLabel>
+//!         </XTooltip>
+//!     </>
+//! }
+//! ```
+//!
+//! Anchor is described by the tooltip with `aria-describedby`. Error tooltips are also linked with
+//! `aria-errormessage`, and are kept opened while the anchor is focused and invalid (`:invalid`
+//! or `aria-invalid="true"`).
+//!
 //! > Enable `"element-x-tooltip"` feature to use this component.
-
-// TODO: Implement animation with Element.animate method once
-// https://github.com/rustwasm/wasm-bindgen/pull/3142 merged
-// TODO: Update position after resize the window
+use std::cell::Cell;
 use std::fmt::Write;
+use std::time::Duration;
+
+use prokio::time::sleep;
+use wasm_bindgen::JsCast;
+use web_sys::{window, Element, HtmlElement, KeyboardEvent, PointerEvent};
 use yew::{html, Children, Component, NodeRef, Properties};
 
+use crate::positioning::{compute_position, Alignment, PositionOptions, Rect, Side};
+use crate::utils::{
+    client_rect, min_pressed_time, new_animation, next_id, property_keyframes, window_rect,
+    EventListener, ResizeListener,
+};
 use crate::{CSSEasing, Transition};
 
-#[cfg(feature = "element-x-button")]
-const WINDOW_WHITESPACE: f64 = 8.0;
-#[cfg(feature = "element-x-button")]
 const SPACE_BETWEEN: f64 = 8.0;
+/// Minimum distance of the arrow from the corners of the tooltip
+const ARROW_MARGIN: f64 = 8.0;
+/// Time (in milliseconds) after closing a tooltip, which the next tooltip is opened without the
+/// open delay
+const WARM_UP_DURATION: f64 = 500.0;
+
+thread_local! {
+    /// Number of the displayed tooltips, and when the last tooltip was closed
+    static WARM_UP: Cell<(u32, f64)> = const { Cell::new((0, f64::NEG_INFINITY)) };
+}
+
+/// Whether a tooltip is displayed or was closed recently. Then moving to another anchor should
+/// display its tooltip immediately.
+fn is_warm() -> bool {
+    let (displayed, closed_at) = WARM_UP.with(|warm_up| warm_up.get());
+    displayed > 0 || js_sys::Date::now() - closed_at < WARM_UP_DURATION
+}
+
+fn set_displayed(displayed: bool) {
+    WARM_UP.with(|warm_up| {
+        let (count, closed_at) = warm_up.get();
+        warm_up.set(if displayed {
+            (count + 1, closed_at)
+        } else {
+            (count.saturating_sub(1), js_sys::Date::now())
+        });
+    });
+}
 
 /// Type of the XTooltip component
 #[derive(PartialEq)]
 pub enum XTooltipType {
     Hint,
+    /// Validation error of the anchor. Linked with `aria-errormessage` and kept opened while the
+    /// anchor is focused and invalid.
     Error,
 }
 
@@ -60,13 +122,67 @@ impl ToString for XTooltipType {
     }
 }
 
-/// Side to display the XTooltip
-#[derive(PartialEq, Clone)]
+/// Side to display the XTooltip, and how it is aligned along that side. `Left`, `Right`, `Top`
+/// and `Bottom` are centered on the anchor. `*Start` variants are aligned to the left (or top)
+/// edge of the anchor, and `*End` variants to the right (or bottom) edge. New placements may be
+/// added later, so matches on this enum need a wildcard arm.
+#[derive(PartialEq, Clone, Copy, Debug)]
+#[non_exhaustive]
 pub enum XTooltipAlign {
     Left,
+    LeftStart,
+    LeftEnd,
     Right,
+    RightStart,
+    RightEnd,
     Top,
+    TopStart,
+    TopEnd,
     Bottom,
+    BottomStart,
+    BottomEnd,
+}
+
+impl XTooltipAlign {
+    /// Side of the anchor, as the centered variant
+    pub fn side(&self) -> XTooltipAlign {
+        let (side, _) = self.placement();
+        Self::from_placement(side, Alignment::Center)
+    }
+
+    fn placement(&self) -> (Side, Alignment) {
+        match self {
+            Self::Left => (Side::Left, Alignment::Center),
+            Self::LeftStart => (Side::Left, Alignment::Start),
+            Self::LeftEnd => (Side::Left, Alignment::End),
+            Self::Right => (Side::Right, Alignment::Center),
+            Self::RightStart => (Side::Right, Alignment::Start),
+            Self::RightEnd => (Side::Right, Alignment::End),
+            Self::Top => (Side::Top, Alignment::Center),
+            Self::TopStart => (Side::Top, Alignment::Start),
+            Self::TopEnd => (Side::Top, Alignment::End),
+            Self::Bottom => (Side::Bottom, Alignment::Center),
+            Self::BottomStart => (Side::Bottom, Alignment::Start),
+            Self::BottomEnd => (Side::Bottom, Alignment::End),
+        }
+    }
+
+    fn from_placement(side: Side, alignment: Alignment) -> Self {
+        match (side, alignment) {
+            (Side::Left, Alignment::Center) => Self::Left,
+            (Side::Left, Alignment::Start) => Self::LeftStart,
+            (Side::Left, Alignment::End) => Self::LeftEnd,
+            (Side::Right, Alignment::Center) => Self::Right,
+            (Side::Right, Alignment::Start) => Self::RightStart,
+            (Side::Right, Alignment::End) => Self::RightEnd,
+            (Side::Top, Alignment::Center) => Self::Top,
+            (Side::Top, Alignment::Start) => Self::TopStart,
+            (Side::Top, Alignment::End) => Self::TopEnd,
+            (Side::Bottom, Alignment::Center) => Self::Bottom,
+            (Side::Bottom, Alignment::Start) => Self::BottomStart,
+            (Side::Bottom, Alignment::End) => Self::BottomEnd,
+        }
+    }
 }
 
 impl Default for XTooltipAlign {
@@ -77,27 +193,50 @@ impl Default for XTooltipAlign {
 
 impl ToString for XTooltipAlign {
     fn to_string(&self) -> String {
-        match self {
-            XTooltipAlign::Left => String::from("left"),
-            XTooltipAlign::Right => String::from("right"),
-            XTooltipAlign::Top => String::from("top"),
-            XTooltipAlign::Bottom => String::from("bottom"),
+        let (side, alignment) = self.placement();
+        let side = match side {
+            Side::Left => "left",
+            Side::Right => "right",
+            Side::Top => "top",
+            Side::Bottom => "bottom",
+        };
+        match alignment {
+            Alignment::Start => format!("{}-start", side),
+            Alignment::Center => String::from(side),
+            Alignment::End => format!("{}-end", side),
         }
     }
 }
 
+/// Where an opened tooltip was placed, after moving it to a side which has enough space
+#[derive(PartialEq, Clone, Debug)]
+pub struct XTooltipPlacement {
+    /// Resolved side and alignment
+    pub align: XTooltipAlign,
+    /// Client coordinates of the tooltip
+    pub x: f64,
+    pub y: f64,
+    /// Distance of the arrow from the left (or top) edge of the tooltip. Arrow keeps pointing to
+    /// the anchor even when the tooltip is moved to fit inside the window.
+    pub arrow_offset: f64,
+}
+
 /// Properties to XTooltip component.
-///
-/// > NOTE: If you are using the XTooltip without `"element-x-button"` feature, it is mandatory
-/// > to define position in `style` prop.
 #[derive(PartialEq, Properties)]
 pub struct XTooltipProps {
-    /// Whether the tooltip is opened or not.
-    ///
-    /// > NOTE: Please implement on_open and on_close callbacks
-    /// > when handling the open state manually. Otherwise the tooltip will not open with x-button
-    /// > mouseenter event.
+    /// Whether the tooltip is opened or not. When given, the tooltip is only opened and closed by
+    /// this prop, and `on_open` and `on_close` are just reporting the anchor events. Otherwise
+    /// the tooltip is opened by hovering or focusing the anchor.
+    #[prop_or_default]
     pub open: Option<bool>,
+    /// Identifier of the tooltip element, which the anchor refers in `aria-describedby`.
+    /// Generated when not given.
+    #[prop_or_default]
+    pub id: Option<String>,
+    /// Element which the tooltip is attached to. Parent element of the tooltip is used when not
+    /// given.
+    #[prop_or_default]
+    pub anchor: Option<NodeRef>,
     /// Whether the tooltip is disabled or not
     #[prop_or_default]
     pub disabled: bool,
@@ -108,18 +247,24 @@ pub struct XTooltipProps {
     ///
     /// > NOTE: This behaviour will be ignored if it
     /// > overflowing from the screen. Default value is "bottom"
-    #[cfg(feature = "element-x-button")]
     #[prop_or_default]
     pub align: XTooltipAlign,
     /// Inner contents to display in tooltip
     #[prop_or_default]
     pub children: Children,
-    /// When opening with x-button mouseenter event
-    #[cfg(feature = "element-x-button")]
+    /// Time (in milliseconds) to hover the anchor before opening. Ignored when another tooltip
+    /// was displayed just before.
+    #[prop_or(500)]
+    pub open_delay: u64,
+    /// Time (in milliseconds) to keep the tooltip opened after leaving the anchor
+    #[prop_or(100)]
+    pub close_delay: u64,
+    /// When the anchor is hovered, focused with the keyboard or long pressed. Receives the
+    /// placement which the tooltip is going to be displayed.
     #[prop_or_default]
-    pub on_open: Option<yew::Callback<()>>,
-    /// When closing with x-button mouseleave event
-    #[cfg(feature = "element-x-button")]
+    pub on_open: Option<yew::Callback<XTooltipPlacement>>,
+    /// When the anchor is left, blurred, the tooltip is dismissed with Escape or it is disabled
+    /// while opened
     #[prop_or_default]
     pub on_close: Option<yew::Callback<()>>,
     /// Styles to apply for root element of tooltip
@@ -138,173 +283,456 @@ pub struct XTooltipProps {
 
 #[doc(hidden)]
 pub enum XTooltipMessage {
-    #[cfg(feature = "element-x-button")]
-    XButtonContextUpdated(crate::xbutton::XButtonContext),
-    #[cfg(feature = "element-x-button")]
-    XButtonCloseAnimationEnd,
+    /// Pointer or focus event of the anchor
+    AnchorEvent(web_sys::Event),
+    /// Delayed opening (`true`) or closing. Ignored when another one is scheduled after it.
+    Toggle(u32, bool),
+    /// Escape was pressed or the anchor was clicked
+    Dismiss,
+    CloseAnimationEnd,
+    /// Window resized, something scrolled or the anchor resized while opened
+    Reposition,
 }
 
 /// XTooltip Element
 pub struct XTooltip {
-    #[cfg(feature = "element-x-button")]
-    position: Option<web_sys::DomRect>,
-    #[cfg(feature = "element-x-button")]
+    position: Option<XTooltipPlacement>,
+    /// Open state when it is not controlled by the `open` prop
     open: bool,
-    #[cfg(feature = "element-x-button")]
+    /// Tooltip is displayed (opened or closing)
+    shown: bool,
+    /// Close animation is running
+    closing: bool,
+    /// Listeners of the anchor should be attached after rendering
+    attach_pending: bool,
+    /// Identifier of the last scheduled opening or closing
+    toggle_id: u32,
+    /// Dismissed until the anchor is left
+    dismissed: bool,
+    /// Identifier used when the `id` prop is not given
+    generated_id: String,
+    /// Element which the tooltip is pointing to
+    anchor: Option<Element>,
     node_ref: NodeRef,
-    #[cfg(feature = "element-x-button")]
-    _x_button_context_listener: Option<yew::context::ContextHandle<crate::xbutton::XButtonContext>>,
+    /// Hover and focus listeners of the anchor
+    _triggers: Vec<EventListener>,
+    /// Listeners to reposition the tooltip. Only kept while opened.
+    _listeners: Vec<EventListener>,
+    _resize_listener: Option<ResizeListener>,
 }
 
-impl Component for XTooltip {
-    type Message = XTooltipMessage;
-    type Properties = XTooltipProps;
+impl XTooltip {
+    fn is_open(&self, ctx: &yew::Context<Self>) -> bool {
+        ctx.props().open.unwrap_or(self.open)
+    }
 
-    #[cfg(not(feature = "element-x-button"))]
-    fn create(_ctx: &yew::Context<Self>) -> Self {
-        XTooltip {}
+    fn id(&self, ctx: &yew::Context<Self>) -> String {
+        ctx.props()
+            .id
+            .clone()
+            .unwrap_or_else(|| self.generated_id.clone())
     }
 
-    #[cfg(feature = "element-x-button")]
-    fn create(ctx: &yew::Context<Self>) -> Self {
-        if let Some((_message, context_listener)) = ctx
-            .link()
-            .context(ctx.link().callback(XTooltipMessage::XButtonContextUpdated))
+    /// Error tooltip should stay opened while the anchor is focused and invalid
+    fn is_held_open(&self, ctx: &yew::Context<Self>) -> bool {
+        let anchor = match &self.anchor {
+            Some(anchor) if ctx.props().r#type == XTooltipType::Error => anchor,
+            _ => return false,
+        };
+        let focused = anchor.matches(":focus-within").unwrap_or(false);
+        let invalid = anchor.matches(":invalid").unwrap_or(false)
+            || anchor.get_attribute("aria-invalid").as_deref() == Some("true");
+        focused && invalid
+    }
+
+    /// Refers the tooltip from the ARIA attributes of the anchor
+    fn link_anchor(&self, ctx: &yew::Context<Self>) {
+        let anchor = match &self.anchor {
+            Some(anchor) => anchor,
+            None => return,
+        };
+        let id = self.id(ctx);
+        let described_by = anchor.get_attribute("aria-describedby").unwrap_or_default();
+        if !described_by.split_whitespace().any(|token| token == id) {
+            let described_by = format!("{} {}", described_by, id);
+            anchor
+                .set_attribute("aria-describedby", described_by.trim())
+                .unwrap();
+        }
+        if ctx.props().r#type == XTooltipType::Error {
+            anchor.set_attribute("aria-errormessage", &id).unwrap();
+        }
+    }
+
+    /// Removes the references added by `link_anchor`
+    fn unlink_anchor(&self, id: &str) {
+        let anchor = match &self.anchor {
+            Some(anchor) => anchor,
+            None => return,
+        };
+        if let Some(described_by) = anchor.get_attribute("aria-describedby") {
+            let described_by: Vec<&str> = described_by
+                .split_whitespace()
+                .filter(|token| *token != id)
+                .collect();
+            if described_by.is_empty() {
+                anchor.remove_attribute("aria-describedby").unwrap();
+            } else {
+                anchor
+                    .set_attribute("aria-describedby", &described_by.join(" "))
+                    .unwrap();
+            }
+        }
+        if anchor.get_attribute("aria-errormessage").as_deref() == Some(id) {
+            anchor.remove_attribute("aria-errormessage").unwrap();
+        }
+    }
+
+    fn anchor_element(&self, ctx: &yew::Context<Self>) -> Option<Element> {
+        match &ctx.props().anchor {
+            Some(anchor) => anchor.cast::<Element>(),
+            None => self
+                .node_ref
+                .cast::<Element>()
+                .and_then(|element| element.parent_element()),
+        }
+    }
+
+    /// Opens and closes the tooltip with the hover, focus and long press of the anchor
+    fn attach_triggers(&mut self, ctx: &yew::Context<Self>) {
+        self.anchor = self.anchor_element(ctx);
+        self.link_anchor(ctx);
+        let anchor = match &self.anchor {
+            Some(anchor) => anchor.clone(),
+            None => {
+                self._triggers = Vec::new();
+                return;
+            }
+        };
+
+        self._triggers = [
+            "pointerenter",
+            "pointerleave",
+            "pointerdown",
+            "pointerup",
+            "pointercancel",
+            "focusin",
+            "focusout",
+            "input",
+        ]
+        .into_iter()
+        .map(|event_type| {
+            let link = ctx.link().clone();
+            EventListener::new(&anchor, event_type, move |e| {
+                link.send_message(XTooltipMessage::AnchorEvent(e))
+            })
+        })
+        .collect();
+    }
+
+    /// Opens or closes after the given delay. Replaces the previously scheduled one.
+    fn schedule(&mut self, ctx: &yew::Context<Self>, open: bool, delay: u64) -> bool {
+        self.toggle_id += 1;
+        if delay == 0 {
+            return self.set_open(ctx, open);
+        }
+        let id = self.toggle_id;
+        ctx.link().send_future(async move {
+            sleep(Duration::from_millis(delay)).await;
+            XTooltipMessage::Toggle(id, open)
+        });
+        false
+    }
+
+    /// Closes after the close delay
+    fn hide(&mut self, ctx: &yew::Context<Self>) -> bool {
+        self.dismissed = false;
+        self.schedule(ctx, false, ctx.props().close_delay)
+    }
+
+    /// Closes immediately and keeps closed until the anchor is left
+    fn dismiss(&mut self, ctx: &yew::Context<Self>) -> bool {
+        self.toggle_id += 1;
+        self.dismissed = true;
+        self.set_open(ctx, false)
+    }
+
+    fn set_open(&mut self, ctx: &yew::Context<Self>, open: bool) -> bool {
+        let props = ctx.props();
+        if open == self.is_open(ctx) || (open && props.disabled) {
+            return false;
+        }
+        if !open && !self.dismissed && self.is_held_open(ctx) {
+            return false;
+        }
+        if open {
+            if let Some(on_open) = props.on_open.clone() {
+                // Placement is measured before the tooltip is displayed
+                if self.reposition(ctx) {
+                    on_open.emit(self.position.clone().unwrap());
+                }
+            }
+        } else if let Some(on_close) = &props.on_close {
+            on_close.emit(());
+        }
+        self.open = open;
+        props.open.is_none()
+    }
+
+    /// Measures the tooltip and the anchor, then calculates the position again. Returns `false`
+    /// when there is nothing to position.
+    fn reposition(&mut self, ctx: &yew::Context<Self>) -> bool {
+        let (current_element, anchor) = match (self.node_ref.cast::<HtmlElement>(), &self.anchor)
         {
-            XTooltip {
-                _x_button_context_listener: Some(context_listener),
-                open: false,
-                position: None,
-                node_ref: NodeRef::default(),
+            (Some(current_element), Some(anchor)) => (current_element, anchor),
+            _ => return false,
+        };
+
+        // Measure the tooltip as displayed, then restore the rendered style
+        let style = ctx.props().style.clone().unwrap_or(String::from(""));
+        let rendered_style = current_element.get_attribute("style");
+        current_element
+            .set_attribute(
+                "style",
+                &format!("{}; display:block; position: fixed; opacity: 0; top:0; left:0", &style),
+            )
+            .unwrap();
+        let current_rect = current_element.get_bounding_client_rect();
+        match rendered_style {
+            Some(rendered_style) => current_element.set_attribute("style", &rendered_style),
+            None => current_element.remove_attribute("style"),
+        }
+        .unwrap();
+
+        let position = calculate_position(
+            client_rect(anchor),
+            current_rect.width(),
+            current_rect.height(),
+            ctx.props().align,
+        );
+        self.position = Some(position);
+        true
+    }
+
+    /// Keeps the tooltip next to the anchor while it is opened
+    fn listen_changes(&mut self, ctx: &yew::Context<Self>) {
+        let win = window().unwrap();
+        let link = ctx.link().clone();
+        let on_resize = move |_| link.send_message(XTooltipMessage::Reposition);
+        let link = ctx.link().clone();
+        // Scroll events are not bubbling, so any scroll ancestor is caught in the capture phase
+        let on_scroll = move |_| link.send_message(XTooltipMessage::Reposition);
+        let link = ctx.link().clone();
+        let on_keydown = move |e: web_sys::Event| {
+            if let Some(e) = e.dyn_ref::<KeyboardEvent>() {
+                if e.key() == "Escape" {
+                    link.send_message(XTooltipMessage::Dismiss);
+                }
             }
-        } else {
-            XTooltip {
-                _x_button_context_listener: None,
-                open: false,
-                position: None,
-                node_ref: NodeRef::default(),
+        };
+        self._listeners = vec![
+            EventListener::new(&win, "resize", on_resize),
+            EventListener::with_capture(&win, "scroll", true, on_scroll),
+            EventListener::with_capture(&win, "keydown", true, on_keydown),
+        ];
+
+        let link = ctx.link().clone();
+        self._resize_listener = self.anchor.as_ref().map(|anchor| {
+            ResizeListener::new(anchor, move || {
+                link.send_message(XTooltipMessage::Reposition)
+            })
+        });
+    }
+
+    fn animate_open(&self, ctx: &yew::Context<Self>) {
+        let props = ctx.props();
+        if props.open_transition.property == "opacity" {
+            if let Some(current_element) = self.node_ref.cast::<Element>() {
+                new_animation(
+                    &current_element,
+                    &property_keyframes("opacity", &["0", "1"]),
+                    props.open_transition.duration,
+                    &props.open_transition.easing,
+                );
             }
         }
     }
 
-    #[cfg(feature = "element-x-button")]
-    fn update(&mut self, ctx: &yew::Context<Self>, msg: Self::Message) -> bool {
-        use std::collections::HashMap;
+    /// Fades out the tooltip, then hides it
+    fn start_close(&mut self, ctx: &yew::Context<Self>) {
         use wasm_bindgen_futures::JsFuture;
-        use yew::TargetCast;
 
-        use js_sys::Object;
-        use web_sys::{window, Element};
+        self.closing = true;
+        let props = ctx.props();
+        let current_element = self.node_ref.cast::<Element>();
+        match current_element {
+            Some(current_element) if props.close_transition.property == "opacity" => {
+                let animate = new_animation(
+                    &current_element,
+                    &property_keyframes("opacity", &["1", "0"]),
+                    props.close_transition.duration,
+                    &props.close_transition.easing,
+                );
 
-        use crate::xbutton::XButtonEvent;
+                ctx.link().send_future(async move {
+                    JsFuture::from(animate.finished().unwrap()).await.ok();
+                    XTooltipMessage::CloseAnimationEnd
+                });
+            }
+            _ => ctx.link().send_message(XTooltipMessage::CloseAnimationEnd),
+        }
+    }
 
-        let align = ctx.props().align.clone();
-        let style = ctx.props().style.clone().unwrap_or(String::from(""));
+    /// Drops the cached position and the listeners
+    fn close(&mut self) {
+        if self.shown {
+            set_displayed(false);
+        }
+        self.shown = false;
+        self.closing = false;
+        self.position = None;
+        self._listeners.clear();
+        self._resize_listener = None;
+    }
+}
+
+impl Component for XTooltip {
+    type Message = XTooltipMessage;
+    type Properties = XTooltipProps;
 
+    fn create(_ctx: &yew::Context<Self>) -> Self {
+        XTooltip {
+            position: None,
+            open: false,
+            shown: false,
+            closing: false,
+            attach_pending: true,
+            toggle_id: 0,
+            dismissed: false,
+            generated_id: next_id("x-tooltip"),
+            anchor: None,
+            node_ref: NodeRef::default(),
+            _triggers: Vec::new(),
+            _listeners: Vec::new(),
+            _resize_listener: None,
+        }
+    }
+
+    fn update(&mut self, ctx: &yew::Context<Self>, msg: Self::Message) -> bool {
+        let props = ctx.props();
         match msg {
-            XTooltipMessage::XButtonContextUpdated(message) => {
-                if let Some(event) = &message.event {
-                    match event {
-                        XButtonEvent::MouseEnter(e) => {
-                            let current_element =
-                                self.node_ref.cast::<web_sys::HtmlElement>().unwrap();
-                            if !self.open {
-                                let props = ctx.props();
-                                if props.on_open.is_some() {
-                                    props.on_open.clone().unwrap().emit(());
-                                }
-
-                                if props.close_transition.property == "opacity" {
-                                    let mut keyframes = HashMap::new();
-                                    keyframes.insert("opacity", ["0", "1"]);
-                                    crate::utils::new_animation(
-                                        &current_element,
-                                        &Object::try_from(
-                                            &serde_wasm_bindgen::to_value(&keyframes).unwrap(),
-                                        )
-                                        .unwrap(),
-                                        props.close_transition.duration,
-                                        &props.close_transition.easing,
-                                    );
-                                }
-                            }
-                            self.open = true;
-                            if self.position.is_none() {
-                                if let Some(element) = e.target_dyn_into::<web_sys::HtmlElement>() {
-                                    current_element
-                                        .set_attribute(
-                                            "style",
-                                            &format!("{}; display:block; position: fixed; opacity: 0; top:0; left:0", &style),
-                                        )
-                                        .unwrap();
-                                    let current_rect = current_element.get_bounding_client_rect();
-                                    let current_width = current_rect.width();
-                                    let current_height = current_rect.height();
-                                    current_element.set_attribute("style", &style).unwrap();
-
-                                    let win = window().unwrap();
-
-                                    let target_rect = element.get_bounding_client_rect();
-
-                                    let window_height =
-                                        win.inner_height().unwrap().as_f64().unwrap();
-                                    let window_width = win.inner_width().unwrap().as_f64().unwrap();
-
-                                    let position = calculate_position(
-                                        target_rect,
-                                        current_width,
-                                        current_height,
-                                        window_width,
-                                        window_height,
-                                        align,
-                                        None,
-                                        0,
-                                    );
-                                    self.position = Some(position);
-                                }
-                            }
-                            true
-                        }
-                        XButtonEvent::MouseLeave(_e) => {
-                            if self.open {
-                                let props = ctx.props();
-                                if props.on_close.is_some() {
-                                    props.on_close.clone().unwrap().emit(());
-                                }
-
-                                if props.close_transition.property == "opacity" {
-                                    let current_element = self.node_ref.cast::<Element>().unwrap();
-                                    let mut keyframes = HashMap::new();
-                                    keyframes.insert("opacity", ["1", "0"]);
-                                    let animate = crate::utils::new_animation(
-                                        &current_element,
-                                        &Object::try_from(
-                                            &serde_wasm_bindgen::to_value(&keyframes).unwrap(),
-                                        )
-                                        .unwrap(),
-                                        props.close_transition.duration,
-                                        &props.close_transition.easing,
-                                    );
-
-                                    ctx.link().send_future(async move {
-                                        JsFuture::from(animate.finished().unwrap()).await.unwrap();
-                                        XTooltipMessage::XButtonCloseAnimationEnd
-                                    });
-                                }
-                            }
-                            true
+            XTooltipMessage::AnchorEvent(e) => {
+                let touch = e
+                    .dyn_ref::<PointerEvent>()
+                    .map(|e| e.pointer_type() == "touch")
+                    .unwrap_or(false);
+                match e.type_().as_str() {
+                    // Touch pointers are opening with a long press instead
+                    "pointerenter" | "focusin" if touch || props.disabled || self.dismissed => {
+                        false
+                    }
+                    "pointerenter" => {
+                        let delay = if is_warm() { 0 } else { props.open_delay };
+                        self.schedule(ctx, true, delay)
+                    }
+                    // Only the keyboard focus is opening, clicking is focusing the anchor too.
+                    // Errors are displayed with any focus.
+                    "focusin" => {
+                        let keyboard = self
+                            .anchor
+                            .as_ref()
+                            .and_then(|anchor| anchor.matches(":focus-visible").ok())
+                            .unwrap_or(true);
+                        (keyboard || self.is_held_open(ctx)) && self.schedule(ctx, true, 0)
+                    }
+                    // Error is displayed as soon as the value becomes invalid, and hidden when
+                    // it is corrected
+                    "input" if props.r#type == XTooltipType::Error => {
+                        if self.is_held_open(ctx) {
+                            !props.disabled && !self.dismissed && self.schedule(ctx, true, 0)
+                        } else {
+                            self.schedule(ctx, false, 0)
                         }
-                        _ => false,
                     }
-                } else {
-                    false
+                    "pointerdown" if touch && !props.disabled => {
+                        self.schedule(ctx, true, min_pressed_time("touch"))
+                    }
+                    // Clicking dismisses the tooltip until the anchor is left
+                    "pointerdown" if !self.is_held_open(ctx) => self.dismiss(ctx),
+                    "pointerleave" if !touch => self.hide(ctx),
+                    "pointerup" | "pointercancel" if touch => self.hide(ctx),
+                    "focusout" => self.hide(ctx),
+                    _ => false,
                 }
             }
-            XTooltipMessage::XButtonCloseAnimationEnd => {
-                self.open = false;
+            XTooltipMessage::Toggle(id, open) => id == self.toggle_id && self.set_open(ctx, open),
+            XTooltipMessage::Dismiss => self.dismiss(ctx),
+            XTooltipMessage::CloseAnimationEnd => {
+                // Opened again while fading out
+                if !self.closing {
+                    return false;
+                }
+                self.close();
                 true
             }
+            XTooltipMessage::Reposition => self.shown && self.reposition(ctx),
+        }
+    }
+
+    fn changed(&mut self, ctx: &yew::Context<Self>, old_props: &Self::Properties) -> bool {
+        let props = ctx.props();
+        if props.anchor != old_props.anchor
+            || props.id != old_props.id
+            || props.r#type != old_props.r#type
+        {
+            let old_id = old_props
+                .id
+                .clone()
+                .unwrap_or_else(|| self.generated_id.clone());
+            self.unlink_anchor(&old_id);
+            if props.anchor != old_props.anchor {
+                self.attach_pending = true;
+            } else {
+                self.link_anchor(ctx);
+            }
         }
+        if props.disabled && !old_props.disabled {
+            // Cancel the pending open, and let the owner of a controlled tooltip know
+            self.toggle_id += 1;
+            if self.is_open(ctx) {
+                if let Some(on_close) = &props.on_close {
+                    on_close.emit(());
+                }
+            }
+            self.open = false;
+        }
+        true
+    }
+
+    fn rendered(&mut self, ctx: &yew::Context<Self>, _first_render: bool) {
+        if self.attach_pending {
+            self.attach_pending = false;
+            self.attach_triggers(ctx);
+        }
+
+        let open = self.is_open(ctx) && !ctx.props().disabled;
+        if open && !self.shown {
+            self.shown = true;
+            set_displayed(true);
+            self.listen_changes(ctx);
+            self.animate_open(ctx);
+            ctx.link().send_message(XTooltipMessage::Reposition);
+        } else if open && self.closing {
+            self.closing = false;
+            self.animate_open(ctx);
+        } else if !open && self.shown && !self.closing {
+            self.start_close(ctx);
+        }
+    }
+
+    fn destroy(&mut self, ctx: &yew::Context<Self>) {
+        self.unlink_anchor(&self.id(ctx));
+        self.close();
     }
 
     fn view(&self, ctx: &yew::Context<Self>) -> yew::Html {
@@ -312,6 +740,7 @@ impl Component for XTooltip {
         let mut classes = String::from("x-tooltip");
 
         if let Some(user_class) = props.class.clone() {
+            classes.push(' ');
             classes.push_str(user_class.trim());
         }
 
@@ -320,7 +749,6 @@ impl Component for XTooltip {
         }
 
         write!(&mut classes, " type-{}", props.r#type.to_string()).unwrap();
-        #[cfg(feature = "element-x-button")]
         write!(&mut classes, " align-{}", props.align.to_string()).unwrap();
 
         let mut style = String::new();
@@ -328,35 +756,45 @@ impl Component for XTooltip {
         if let Some(user_style) = props.style.clone() {
             style.push_str(&user_style);
             if !user_style.trim().ends_with(";") {
-                style.push_str(";");
+                style.push(';');
             }
         }
 
-        #[cfg(feature = "element-x-button")]
-        if (props.open.is_some() && props.open.unwrap() || props.open.is_none() && self.open)
-            && self.position.is_some()
-        {
-            let position = self.position.clone().unwrap();
+        if let Some(position) = self.position.as_ref().filter(|_| self.shown) {
+            if self.closing {
+                classes.push_str(" animating");
+            } else {
+                classes.push_str(" opened");
+            }
+            write!(&mut classes, " side-{}", position.align.side().to_string()).unwrap();
             write!(
                 &mut style,
-                "top:{}px;left:{}px;display:block",
-                position.y().round() as u32,
-                position.x().round() as u32
+                "top:{}px;left:{}px",
+                position.y.round() as i32,
+                position.x.round() as i32
             )
             .unwrap();
         }
-        #[cfg(not(feature = "element-x-button"))]
-        if props.open.is_some() && props.open.unwrap() {
-            style.push_str("display: block;")
-        }
 
-        #[cfg(not(feature = "element-x-button"))]
-        let node_ref = NodeRef::default();
-        #[cfg(feature = "element-x-button")]
-        let node_ref = self.node_ref.clone();
+        let arrow = self.position.as_ref().filter(|_| self.shown).map(|position| {
+            let style = match position.align.side() {
+                XTooltipAlign::Top | XTooltipAlign::Bottom => {
+                    format!("left:{}px", position.arrow_offset.round())
+                }
+                _ => format!("top:{}px", position.arrow_offset.round()),
+            };
+            html! { <div class="x-tooltip-arrow" {style}></div> }
+        });
 
         html! {
-            <div ref={node_ref} class={classes} style={style}>
+            <div
+                ref={self.node_ref.clone()}
+                id={self.id(ctx)}
+                role="tooltip"
+                aria-live={(props.r#type == XTooltipType::Error).then_some("assertive")}
+                class={classes}
+                style={style}>
+                {for arrow}
                 <div class="x-tooltip-contents">
                     {for props.children.iter()}
                 </div>
@@ -365,161 +803,32 @@ impl Component for XTooltip {
     }
 }
 
-#[cfg(feature = "element-x-button")]
+/// Places the tooltip on the preferred side of the target, or on the first other side which has
+/// enough space. Tooltip is moved along the side to stay inside the window.
 fn calculate_position(
-    target_rect: web_sys::DomRect,
+    target_rect: Rect,
     tool_width: f64,
     tool_height: f64,
-    win_width: f64,
-    win_height: f64,
     align: XTooltipAlign,
-    from: Option<XTooltipAlign>,
-    try_count: u8,
-) -> web_sys::DomRect {
-    use web_sys::DomRect;
-    if try_count > 3 {
-        return target_rect;
-    }
-
-    let target_bottom = target_rect.bottom();
-    let target_top = target_rect.top();
-    let target_left = target_rect.left();
-    let target_right = target_rect.right();
-    let target_width = target_rect.width();
-    let target_height = target_rect.height();
-
-    let other_axis = match align {
-        XTooltipAlign::Bottom | XTooltipAlign::Top => {
-            let mut x = target_left - (tool_width - target_width) / 2.0;
-            if x < WINDOW_WHITESPACE {
-                x = WINDOW_WHITESPACE;
-            }
-            if target_right + (tool_width - target_width) / 2.0 + SPACE_BETWEEN + WINDOW_WHITESPACE
-                > win_width
-            {
-                x = win_width - tool_width - SPACE_BETWEEN - WINDOW_WHITESPACE;
-            }
-
-            x
-        }
-        XTooltipAlign::Left | XTooltipAlign::Right => {
-            let mut y = target_top - (tool_height - target_height) / 2.0;
-
-            if y < WINDOW_WHITESPACE {
-                y = WINDOW_WHITESPACE;
-            }
-
-            if target_bottom
-                + (tool_height - target_height) / 2.0
-                + SPACE_BETWEEN
-                + WINDOW_WHITESPACE
-                > win_height
-            {
-                y = win_height - tool_height - SPACE_BETWEEN - WINDOW_WHITESPACE;
-            }
-
-            y
-        }
+) -> XTooltipPlacement {
+    let (side, alignment) = align.placement();
+    // Opposite side first, then the sides on the other axis
+    let fallback_sides = if side.is_vertical() {
+        [side.opposite(), Side::Right, Side::Left]
+    } else {
+        [side.opposite(), Side::Bottom, Side::Top]
     };
-
-    match align {
-        XTooltipAlign::Bottom => {
-            if target_bottom + tool_height + SPACE_BETWEEN + WINDOW_WHITESPACE > win_height {
-                return calculate_position(
-                    target_rect,
-                    tool_width,
-                    tool_height,
-                    win_width,
-                    win_height,
-                    if let Some(XTooltipAlign::Top) = from {
-                        XTooltipAlign::Right
-                    } else {
-                        XTooltipAlign::Top
-                    },
-                    Some(align),
-                    try_count + 1,
-                );
-            }
-            let top = target_bottom + SPACE_BETWEEN;
-
-            DomRect::new_with_x_and_y_and_width_and_height(other_axis, top, tool_width, tool_height)
-                .unwrap()
-        }
-        XTooltipAlign::Top => {
-            if target_top - tool_height - SPACE_BETWEEN - WINDOW_WHITESPACE < 0.0 {
-                return calculate_position(
-                    target_rect,
-                    tool_width,
-                    tool_height,
-                    win_width,
-                    win_height,
-                    if let Some(XTooltipAlign::Bottom) = from {
-                        XTooltipAlign::Right
-                    } else {
-                        XTooltipAlign::Bottom
-                    },
-                    Some(align),
-                    try_count + 1,
-                );
-            }
-
-            let top = target_top - SPACE_BETWEEN - tool_height;
-            DomRect::new_with_x_and_y_and_width_and_height(other_axis, top, tool_width, tool_height)
-                .unwrap()
-        }
-        XTooltipAlign::Left => {
-            if target_left - tool_width - SPACE_BETWEEN - WINDOW_WHITESPACE < 0.0 {
-                return calculate_position(
-                    target_rect,
-                    tool_width,
-                    tool_height,
-                    win_width,
-                    win_height,
-                    if let Some(XTooltipAlign::Right) = from {
-                        XTooltipAlign::Bottom
-                    } else {
-                        XTooltipAlign::Right
-                    },
-                    Some(align),
-                    try_count + 1,
-                );
-            }
-
-            let right = target_left - SPACE_BETWEEN - tool_width;
-            DomRect::new_with_x_and_y_and_width_and_height(
-                right,
-                other_axis,
-                tool_width,
-                tool_height,
-            )
-            .unwrap()
-        }
-        XTooltipAlign::Right => {
-            if target_right + tool_width + SPACE_BETWEEN + WINDOW_WHITESPACE > win_width {
-                return calculate_position(
-                    target_rect,
-                    tool_width,
-                    tool_height,
-                    win_width,
-                    win_height,
-                    if let Some(XTooltipAlign::Left) = from {
-                        XTooltipAlign::Bottom
-                    } else {
-                        XTooltipAlign::Left
-                    },
-                    Some(align),
-                    try_count + 1,
-                );
-            }
-
-            let right = target_right + SPACE_BETWEEN;
-            DomRect::new_with_x_and_y_and_width_and_height(
-                right,
-                other_axis,
-                tool_width,
-                tool_height,
-            )
-            .unwrap()
-        }
+    let options = PositionOptions::new(side, alignment, window_rect())
+        .offset(SPACE_BETWEEN)
+        .flip(&fallback_sides)
+        .shift()
+        .arrow(ARROW_MARGIN);
+    let position = compute_position(target_rect, tool_width, tool_height, &options);
+
+    XTooltipPlacement {
+        align: XTooltipAlign::from_placement(position.side, position.alignment),
+        x: position.x,
+        y: position.y,
+        arrow_offset: position.arrow_offset.unwrap_or(0.0),
     }
 }